@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::Parser;
 
 #[derive(Parser)]
@@ -7,7 +9,23 @@ pub struct Cli {
     #[arg(short, long)]
     pub debug: bool,
 
-    /// Desired decimal places accuracy
+    /// Decimal places to display
     #[arg(short='a', long, default_value_t = 8, value_parser = clap::value_parser!(u8).range(1..=15))]
     pub dp: u8,
+
+    /// Number of turns from the start of the game to calculate landing probabilities for
+    #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..))]
+    pub turns: u16,
+
+    /// Board definition file (TOML or JSON) to use instead of the classic board
+    #[arg(short, long)]
+    pub board: Option<PathBuf>,
+
+    /// Dice model file (TOML or JSON) to use instead of two six sided dice
+    #[arg(long)]
+    pub dice: Option<PathBuf>,
+
+    /// Rules file (TOML or JSON) with house rules to use instead of the classic rules
+    #[arg(long)]
+    pub rules: Option<PathBuf>,
 }
@@ -1,23 +1,51 @@
 use std::cmp::max;
 
 use monopoly_lib::calc::transmatrix::TransMatrix;
-use nalgebra::{DMatrix, Matrix};
+use nalgebra::{DMatrix, Matrix, OMatrix};
 
 use crate::matrix::{RenderMatrixCb, render_matrix};
 
-pub fn print_summary<T>(items: Vec<T>, mat: DMatrix<f64>, desc: &str)
+pub fn print_summary<T>(items: Vec<T>, mat: DMatrix<f64>, desc: &str, dp: u8)
 where
     T: std::fmt::Display,
 {
     println!("-------- {desc} --------");
 
-    print_matrix(&mat, None::<Vec<bool>>, Some(items.iter()), "", true);
+    print_matrix(&round_matrix(&mat, dp), None::<Vec<bool>>, Some(items.iter()), "", true);
 }
 
-pub fn print_steady(mat: &TransMatrix, desc: &str) {
+pub fn print_income<T>(items: Vec<T>, colheaders: &[&str], mat: &DMatrix<Option<f64>>, desc: &str, dp: u8)
+where
+    T: std::fmt::Display,
+{
+    println!("-------- {desc} --------");
+
+    let mat = mat.map(|p| p.map_or(String::new(), |p| format!("{p:.0$}", dp as usize)));
+
+    print_matrix(&mat, Some(colheaders), Some(items.iter()), "Set", false);
+}
+
+pub fn print_steady(mat: &TransMatrix, desc: &str, dp: u8) {
     println!("-------- {desc} --------");
 
-    print_matrix(mat.steady(), None::<Vec<bool>>, Some(mat.states().keys()), "", true);
+    print_matrix(
+        &round_matrix(mat.steady(), dp),
+        None::<Vec<bool>>,
+        Some(mat.states_display()),
+        "",
+        true,
+    );
+}
+
+/// Formats each value in a matrix to a number of decimal places
+fn round_matrix<R, C, S>(matrix: &Matrix<f64, R, C, S>, dp: u8) -> OMatrix<String, R, C>
+where
+    R: nalgebra::Dim,
+    C: nalgebra::Dim,
+    S: nalgebra::RawStorage<f64, R, C>,
+    nalgebra::DefaultAllocator: nalgebra::allocator::Allocator<R, C>,
+{
+    matrix.map(|p| format!("{p:.0$}", dp as usize))
 }
 
 // Generic matrix print functions
@@ -1,8 +1,9 @@
 use std::io::Write;
 use std::{error::Error, fs::File, path::Path};
 
+use monopoly_lib::boarddef::BoardDefinition;
+use monopoly_lib::calc::transmatrix::TransMatrix;
 use monopoly_lib::movereason::{IntoEnumIterator, MoveReason};
-use monopoly_lib::{calc::transmatrix::TransMatrix, space::SPACES};
 use nalgebra::{DMatrix, Matrix};
 
 use crate::matrix::render_matrix;
@@ -11,8 +12,8 @@ pub fn write_prob_csv(file: &Path, mat: &TransMatrix, float: bool) -> Result<(),
     write_matrix_csv(
         file,
         mat.combinedmat(),
-        Some(mat.states().keys()),
-        Some(mat.states().keys()),
+        Some(mat.states_display()),
+        Some(mat.states_display()),
         "From \\ To",
         false,
         |p| {
@@ -27,8 +28,8 @@ pub fn write_move_csv(file: &Path, mat: &TransMatrix, float: bool) -> Result<(),
     write_matrix_csv(
         file,
         mat.movemat(),
-        Some(mat.states().keys()),
-        Some(mat.states().keys()),
+        Some(mat.states_display()),
+        Some(mat.states_display()),
         "From \\ To",
         false,
         |p| {
@@ -43,8 +44,8 @@ pub fn write_jump_csv(file: &Path, mat: &TransMatrix, float: bool) -> Result<(),
     write_matrix_csv(
         file,
         mat.jumpmat(),
-        Some(SPACES.iter()),
-        Some(SPACES.iter()),
+        Some(mat.boarddef().spaces().iter()),
+        Some(mat.boarddef().spaces().iter()),
         "From \\ To",
         false,
         |p| {
@@ -55,13 +56,66 @@ pub fn write_jump_csv(file: &Path, mat: &TransMatrix, float: bool) -> Result<(),
     Ok(())
 }
 
-pub fn write_steady_csv(file: &Path, mat: &TransMatrix) -> Result<(), Box<dyn Error>> {
+pub fn write_steady_csv(file: &Path, mat: &TransMatrix, float: bool) -> Result<(), Box<dyn Error>> {
+    if float {
+        write_matrix_csv(
+            file,
+            mat.steady(),
+            None::<Vec<bool>>,
+            Some(mat.states_display()),
+            "",
+            true,
+            |p| p.to_string(),
+        )?;
+    } else {
+        write_matrix_csv(
+            file,
+            mat.steady_exact(),
+            None::<Vec<bool>>,
+            Some(mat.states_display()),
+            "",
+            true,
+            |p| p.to_string(),
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn write_summary_csv<RH>(
+    file: &Path,
+    matrix: &DMatrix<f64>,
+    colheader: &str,
+    rowheaders: RH,
+    rowcolhd: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    RH: IntoIterator + Clone,
+    RH::Item: std::fmt::Display,
+{
+    write_matrix_csv(file, matrix, Some([colheader]), Some(rowheaders), rowcolhd, true, |p| {
+        p.to_string()
+    })?;
+
+    Ok(())
+}
+
+pub fn write_turns_csv<RH>(
+    file: &Path,
+    matrix: &DMatrix<f64>,
+    rowheaders: RH,
+    rowcolhd: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    RH: IntoIterator + Clone,
+    RH::Item: std::fmt::Display,
+{
     write_matrix_csv(
         file,
-        mat.steady(),
-        None::<Vec<bool>>,
-        Some(mat.states().keys()),
-        "",
+        matrix,
+        Some((1..=matrix.nrows()).map(|turn| format!("Turn {turn}"))),
+        Some(rowheaders),
+        rowcolhd,
         true,
         |p| p.to_string(),
     )?;
@@ -69,10 +123,27 @@ pub fn write_steady_csv(file: &Path, mat: &TransMatrix) -> Result<(), Box<dyn Er
     Ok(())
 }
 
-pub fn write_summary_csv<RH>(
+pub fn write_income_csv<RH>(
+    file: &Path,
+    matrix: &DMatrix<Option<f64>>,
+    colheaders: &[&str],
+    rowheaders: RH,
+) -> Result<(), Box<dyn Error>>
+where
+    RH: IntoIterator + Clone,
+    RH::Item: std::fmt::Display,
+{
+    write_matrix_csv(file, matrix, Some(colheaders), Some(rowheaders), "Set", false, |p| {
+        p.map_or(String::new(), |p| p.to_string())
+    })?;
+
+    Ok(())
+}
+
+pub fn write_columns_csv<RH>(
     file: &Path,
     matrix: &DMatrix<f64>,
-    colheader: &str,
+    colheaders: &[&str],
     rowheaders: RH,
     rowcolhd: &str,
 ) -> Result<(), Box<dyn Error>>
@@ -80,14 +151,18 @@ where
     RH: IntoIterator + Clone,
     RH::Item: std::fmt::Display,
 {
-    write_matrix_csv(file, matrix, Some([colheader]), Some(rowheaders), rowcolhd, true, |p| {
+    write_matrix_csv(file, matrix, Some(colheaders), Some(rowheaders), rowcolhd, false, |p| {
         p.to_string()
     })?;
 
     Ok(())
 }
 
-pub fn write_reason_csv<R, C, S>(file: &Path, matrix: &Matrix<f64, R, C, S>) -> Result<(), Box<dyn Error>>
+pub fn write_reason_csv<R, C, S>(
+    file: &Path,
+    boarddef: &BoardDefinition,
+    matrix: &Matrix<f64, R, C, S>,
+) -> Result<(), Box<dyn Error>>
 where
     R: nalgebra::Dim,
     C: nalgebra::Dim,
@@ -97,7 +172,7 @@ where
     write_matrix_csv(
         file,
         matrix,
-        Some(SPACES.iter()),
+        Some(boarddef.spaces().iter()),
         Some(MoveReason::iter().filter(|m| *m as isize >= 0)),
         "Reason \\ Space",
         false,
@@ -2,14 +2,24 @@ use std::{error::Error, path::Path};
 
 use clap::Parser;
 use cli::Cli;
-use console::{print_steady, print_summary};
-use csv::{write_jump_csv, write_move_csv, write_prob_csv, write_reason_csv, write_steady_csv, write_summary_csv};
+use console::{print_income, print_steady, print_summary};
+use csv::{
+    write_columns_csv, write_income_csv, write_jump_csv, write_move_csv, write_prob_csv, write_reason_csv,
+    write_steady_csv, write_summary_csv, write_turns_csv,
+};
+use monopoly_lib::boarddef::BoardDefinition;
+use monopoly_lib::calc::income::SetIncome;
+use monopoly_lib::calc::state::State;
 use monopoly_lib::calc::transmatrix::TransMatrix;
-use monopoly_lib::space::SPACES;
-use monopoly_lib::strategy::Strategy;
+use monopoly_lib::dice::DiceModel;
+use monopoly_lib::rules::Rules;
+use monopoly_lib::space::PropertySet;
+use monopoly_lib::strategy::{JailWait, PayJail};
+use nalgebra::DMatrix;
 use rust_xlsxwriter::Workbook;
 use sheet::{
-    write_jump_sheet, write_move_sheet, write_prob_sheet, write_reason_sheet, write_steady_sheet, write_summary_sheet,
+    write_columns_sheet, write_income_sheet, write_jump_sheet, write_move_sheet, write_prob_sheet, write_reason_sheet,
+    write_steady_sheet, write_summary_sheet, write_turns_sheet,
 };
 
 mod cli;
@@ -18,30 +28,84 @@ mod csv;
 mod matrix;
 mod sheet;
 
+/// Column headings for the cycled deck comparison
+const CYCLED_HEADINGS: [&str; 3] = ["Independent", "Cycled", "Difference"];
+
+/// Column headings for expected rent income
+const INCOME_HEADINGS: [&str; 5] = ["Cost", "Rent per roll", "Rent per turn", "Payback turns", "House ROI"];
+
 fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
 
+    // Load the board layout
+    let boarddef = match &cli.board {
+        Some(file) => BoardDefinition::load(file)?,
+        None => BoardDefinition::default(),
+    };
+
+    // Load the dice model
+    let dice = match &cli.dice {
+        Some(file) => DiceModel::load(file)?,
+        None => DiceModel::classic(),
+    };
+
+    // Load the rule variants
+    let rules = match &cli.rules {
+        Some(file) => Rules::load(file)?,
+        None => Rules::classic(),
+    };
+
     // Calc probabilities when paying to get out of jail
-    let pay_map = TransMatrix::new(Strategy::PayJail, cli.dp, cli.debug);
+    let pay_map = TransMatrix::with_rules(boarddef.clone(), &PayJail, dice.clone(), rules, cli.debug);
     let pay_reason_prob = pay_map.calc_movereason_probabilty();
 
     // Calc probabilities when rolling to get out of jail
-    let wait_map = TransMatrix::new(Strategy::JailWait, cli.dp, cli.debug);
+    let wait_map = TransMatrix::with_rules(boarddef.clone(), &JailWait, dice, rules, cli.debug);
     let wait_reason_prob = wait_map.calc_movereason_probabilty();
 
     // Summarise steady state for pay strategy by board position
     let (pay_space_headings, pay_space_mat) =
-        pay_map.steady_group_sum_split(|state| Some(format!("{}", SPACES[state.position])));
+        pay_map.steady_group_sum_split(|state| Some(format!("{}", boarddef[state.position])));
 
     // Summarise steady state for pay strategy by board set
-    let (pay_set_headings, pay_set_mat) = pay_map.steady_group_sum_split(|state| Some(SPACES[state.position].set()));
+    let (pay_set_headings, pay_set_mat) = pay_map.steady_group_sum_split(|state| Some(boarddef[state.position].set()));
 
     // Summarise steady state for wait strategy by board position
     let (wait_space_headings, wait_space_mat) =
-        wait_map.steady_group_sum_split(|state| Some(format!("{}", SPACES[state.position])));
+        wait_map.steady_group_sum_split(|state| Some(format!("{}", boarddef[state.position])));
 
     // Summarise steady state for wait strategy by board set
-    let (wait_set_headings, wait_set_mat) = wait_map.steady_group_sum_split(|state| Some(SPACES[state.position].set()));
+    let (wait_set_headings, wait_set_mat) =
+        wait_map.steady_group_sum_split(|state| Some(boarddef[state.position].set()));
+
+    // Calculate landing probabilities and expected visits by board position for the first turns of the game
+    let start = State::start(&boarddef);
+    let turns = cli.turns as usize;
+
+    let (turn_spaces, pay_transient_mat) =
+        pay_map.group_columns_sum(&pay_map.transient(&start, turns), |state| Some(state.position));
+    let (_, pay_visits_mat) =
+        pay_map.group_columns_sum(&pay_map.expected_visits(&start, turns), |state| Some(state.position));
+    let (_, wait_transient_mat) =
+        wait_map.group_columns_sum(&wait_map.transient(&start, turns), |state| Some(state.position));
+    let (_, wait_visits_mat) =
+        wait_map.group_columns_sum(&wait_map.expected_visits(&start, turns), |state| Some(state.position));
+
+    let turn_space_headings = turn_spaces.iter().map(|pos| boarddef[*pos]).collect::<Vec<_>>();
+
+    // Calculate the number of turns until first landing on each set which can be bought
+    let (hit_set_headings, pay_hit_mat, pay_hit_dist_mat) = calc_set_hitting(&pay_map, &start, turns);
+    let (_, wait_hit_mat, wait_hit_dist_mat) = calc_set_hitting(&wait_map, &start, turns);
+
+    // Calculate expected rent income for each set and development level
+    let (income_headings, pay_income_mat) = income_matrix(&pay_map.income());
+    let (_, wait_income_mat) = income_matrix(&wait_map.income());
+
+    // Compare landing probabilities by board position when the card decks are cycled to independent draws
+    let pay_cycled_mat = cycled_matrix(&pay_map);
+    let wait_cycled_mat = cycled_matrix(&wait_map);
+
+    let cycled_headings = boarddef.spaces();
 
     // -- Spreadsheet Output --
 
@@ -80,6 +144,58 @@ fn main() -> Result<(), Box<dyn Error>> {
         "Probablility by set (wait)",
     )?;
 
+    // Write worksheets for the first turns for both strategies
+    write_turns_sheet(&mut workbook, &turn_space_headings, &pay_transient_mat, "Pay Turns")?;
+    write_turns_sheet(&mut workbook, &turn_space_headings, &pay_visits_mat, "Pay Visits")?;
+    write_turns_sheet(&mut workbook, &turn_space_headings, &wait_transient_mat, "Wait Turns")?;
+    write_turns_sheet(&mut workbook, &turn_space_headings, &wait_visits_mat, "Wait Visits")?;
+
+    // Write worksheets for the turn each set is first landed on for both strategies
+    write_turns_sheet(
+        &mut workbook,
+        &hit_set_headings,
+        &pay_hit_dist_mat,
+        "Pay Set First Turn",
+    )?;
+    write_turns_sheet(
+        &mut workbook,
+        &hit_set_headings,
+        &wait_hit_dist_mat,
+        "Wait Set First Turn",
+    )?;
+
+    // Write worksheets for expected rent income for both strategies
+    write_income_sheet(
+        &mut workbook,
+        &INCOME_HEADINGS,
+        &income_headings,
+        &pay_income_mat,
+        "Pay Income",
+    )?;
+    write_income_sheet(
+        &mut workbook,
+        &INCOME_HEADINGS,
+        &income_headings,
+        &wait_income_mat,
+        "Wait Income",
+    )?;
+
+    // Write worksheets for the cycled deck comparison for both strategies
+    write_columns_sheet(
+        &mut workbook,
+        &CYCLED_HEADINGS,
+        cycled_headings,
+        &pay_cycled_mat,
+        "Pay Cycled",
+    )?;
+    write_columns_sheet(
+        &mut workbook,
+        &CYCLED_HEADINGS,
+        cycled_headings,
+        &wait_cycled_mat,
+        "Wait Cycled",
+    )?;
+
     // Write worksheets for steady states for both strategies
     write_steady_sheet(&mut workbook, "Pay Steady", &pay_map)?;
     write_steady_sheet(&mut workbook, "Wait Steady", &wait_map)?;
@@ -97,8 +213,8 @@ fn main() -> Result<(), Box<dyn Error>> {
     write_move_sheet(&mut workbook, "Wait Moves Flt", &wait_map, true)?;
 
     // Write worksheets for reason probabilities for both strategies
-    write_reason_sheet(&mut workbook, "Pay Reason", &pay_reason_prob)?;
-    write_reason_sheet(&mut workbook, "Wait Reason", &wait_reason_prob)?;
+    write_reason_sheet(&mut workbook, "Pay Reason", &boarddef, &pay_reason_prob)?;
+    write_reason_sheet(&mut workbook, "Wait Reason", &boarddef, &wait_reason_prob)?;
 
     // Write worksheets for jump probabilities (same for both strategies)
     write_jump_sheet(&mut workbook, "Jumps Frac", &wait_map, false)?;
@@ -145,8 +261,93 @@ fn main() -> Result<(), Box<dyn Error>> {
         "Set",
     )?;
 
+    // Write csv for the first turns for both strategies
+    write_turns_csv(
+        Path::new("csv/pay_turns.csv"),
+        &pay_transient_mat,
+        &turn_space_headings,
+        "Space",
+    )?;
+    write_turns_csv(
+        Path::new("csv/pay_visits.csv"),
+        &pay_visits_mat,
+        &turn_space_headings,
+        "Space",
+    )?;
+    write_turns_csv(
+        Path::new("csv/wait_turns.csv"),
+        &wait_transient_mat,
+        &turn_space_headings,
+        "Space",
+    )?;
+    write_turns_csv(
+        Path::new("csv/wait_visits.csv"),
+        &wait_visits_mat,
+        &turn_space_headings,
+        "Space",
+    )?;
+
+    // Write csv for the turn each set is first landed on for both strategies
+    write_summary_csv(
+        Path::new("csv/pay_set_hitting.csv"),
+        &pay_hit_mat,
+        "Expected turns",
+        &hit_set_headings,
+        "Set",
+    )?;
+    write_summary_csv(
+        Path::new("csv/wait_set_hitting.csv"),
+        &wait_hit_mat,
+        "Expected turns",
+        &hit_set_headings,
+        "Set",
+    )?;
+    write_turns_csv(
+        Path::new("csv/pay_set_first_turn.csv"),
+        &pay_hit_dist_mat,
+        &hit_set_headings,
+        "Set",
+    )?;
+    write_turns_csv(
+        Path::new("csv/wait_set_first_turn.csv"),
+        &wait_hit_dist_mat,
+        &hit_set_headings,
+        "Set",
+    )?;
+
+    // Write csv for expected rent income for both strategies
+    write_income_csv(
+        Path::new("csv/pay_income.csv"),
+        &pay_income_mat,
+        &INCOME_HEADINGS,
+        &income_headings,
+    )?;
+    write_income_csv(
+        Path::new("csv/wait_income.csv"),
+        &wait_income_mat,
+        &INCOME_HEADINGS,
+        &income_headings,
+    )?;
+
+    // Write csv for the cycled deck comparison for both strategies
+    write_columns_csv(
+        Path::new("csv/pay_cycled.csv"),
+        &pay_cycled_mat,
+        &CYCLED_HEADINGS,
+        cycled_headings,
+        "Space",
+    )?;
+    write_columns_csv(
+        Path::new("csv/wait_cycled.csv"),
+        &wait_cycled_mat,
+        &CYCLED_HEADINGS,
+        cycled_headings,
+        "Space",
+    )?;
+
     // Write csv for pay strategy steady state
-    write_steady_csv(Path::new("csv/pay_steady.csv"), &pay_map)?;
+    write_steady_csv(Path::new("csv/pay_steady.csv"), &pay_map, true)?;
+    write_steady_csv(Path::new("csv/pay_steady_frac.csv"), &pay_map, false)?;
 
     // Write csv for move probabilities for pay strategy
     write_move_csv(Path::new("csv/pay_move_frac.csv"), &pay_map, false)?;
@@ -157,7 +358,8 @@ fn main() -> Result<(), Box<dyn Error>> {
     write_prob_csv(Path::new("csv/pay_flt.csv"), &pay_map, true)?;
 
     // Write csv for wait strategy steady state
-    write_steady_csv(Path::new("csv/wait_steady.csv"), &wait_map)?;
+    write_steady_csv(Path::new("csv/wait_steady.csv"), &wait_map, true)?;
+    write_steady_csv(Path::new("csv/wait_steady_frac.csv"), &wait_map, false)?;
 
     // Write csv for move probabilities for wait strategy
     write_move_csv(Path::new("csv/wait_move_frac.csv"), &wait_map, false)?;
@@ -168,8 +370,8 @@ fn main() -> Result<(), Box<dyn Error>> {
     write_prob_csv(Path::new("csv/wait_flt.csv"), &wait_map, true)?;
 
     // Write csv for reason probabilities for both strategies
-    write_reason_csv(Path::new("csv/pay_reason.csv"), &pay_reason_prob)?;
-    write_reason_csv(Path::new("csv/wait_reason.csv"), &wait_reason_prob)?;
+    write_reason_csv(Path::new("csv/pay_reason.csv"), &boarddef, &pay_reason_prob)?;
+    write_reason_csv(Path::new("csv/wait_reason.csv"), &boarddef, &wait_reason_prob)?;
 
     // Write csv for jump probabilities (same for both strategies)
     write_jump_csv(Path::new("csv/jump_frac.csv"), &wait_map, false)?;
@@ -177,23 +379,178 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // -- Console output --
 
+    if rules != Rules::classic() {
+        println!("House rules: {rules}");
+        println!();
+    }
+
     if cli.debug {
         // Write out steady state matrices
-        print_steady(&pay_map, "pay steady state");
-        print_steady(&wait_map, "wait steady state");
+        print_steady(&pay_map, "pay steady state", cli.dp);
+        print_steady(&wait_map, "wait steady state", cli.dp);
     }
 
     // Write summary by space for pay strategy
-    print_summary(pay_space_headings, pay_space_mat, "Probablility by position (pay)");
+    print_summary(
+        pay_space_headings,
+        pay_space_mat,
+        "Probablility by position (pay)",
+        cli.dp,
+    );
 
     // Write summary by set for pay strategy
-    print_summary(pay_set_headings, pay_set_mat, "Probablility by set (pay)");
+    print_summary(pay_set_headings, pay_set_mat, "Probablility by set (pay)", cli.dp);
 
     // Write summary by space for wait strategy
-    print_summary(wait_space_headings, wait_space_mat, "Probablility by position (wait)");
+    print_summary(
+        wait_space_headings,
+        wait_space_mat,
+        "Probablility by position (wait)",
+        cli.dp,
+    );
 
     // Write summary by set for wait strategy
-    print_summary(wait_set_headings, wait_set_mat, "Probablility by set (wait)");
+    print_summary(wait_set_headings, wait_set_mat, "Probablility by set (wait)", cli.dp);
+
+    // Write expected visits by space in the first turns for both strategies
+    print_summary(
+        turn_space_headings.clone(),
+        pay_visits_mat.rows(turns - 1, 1).into_owned(),
+        &format!("Expected visits by position in first {turns} turns (pay)"),
+        cli.dp,
+    );
+
+    print_summary(
+        turn_space_headings,
+        wait_visits_mat.rows(turns - 1, 1).into_owned(),
+        &format!("Expected visits by position in first {turns} turns (wait)"),
+        cli.dp,
+    );
+
+    // Write expected turns until first landing on each set for both strategies
+    print_summary(
+        hit_set_headings.clone(),
+        pay_hit_mat,
+        "Expected turns to first land on set (pay)",
+        cli.dp,
+    );
+
+    print_summary(
+        hit_set_headings,
+        wait_hit_mat,
+        "Expected turns to first land on set (wait)",
+        cli.dp,
+    );
+
+    // Write expected rent income for both strategies
+    print_income(
+        income_headings.clone(),
+        &INCOME_HEADINGS,
+        &pay_income_mat,
+        "Expected rent income (pay)",
+        cli.dp,
+    );
+
+    print_income(
+        income_headings,
+        &INCOME_HEADINGS,
+        &wait_income_mat,
+        "Expected rent income (wait)",
+        cli.dp,
+    );
+
+    // Write the difference in landing probabilities when the card decks are cycled for both strategies
+    print_summary(
+        cycled_headings.to_vec(),
+        pay_cycled_mat.columns(2, 1).transpose(),
+        "Cycled deck difference by position (pay)",
+        cli.dp,
+    );
+
+    print_summary(
+        cycled_headings.to_vec(),
+        wait_cycled_mat.columns(2, 1).transpose(),
+        "Cycled deck difference by position (wait)",
+        cli.dp,
+    );
 
     Ok(())
 }
+
+/// Calculates the probability of landing on each board position with independent card draws and with cycled card
+/// decks, and the difference between them
+fn cycled_matrix(map: &TransMatrix) -> DMatrix<f64> {
+    let independent = map.steady_group_sum(|state| Some(state.position));
+    let cycled = map.group_sum(&map.cycled_steady(), |state| Some(state.position));
+
+    DMatrix::from_fn(map.boarddef().len(), CYCLED_HEADINGS.len(), |pos, col| match col {
+        0 => independent[&pos],
+        1 => cycled[&pos],
+        _ => cycled[&pos] - independent[&pos],
+    })
+}
+
+/// Converts expected rent income to a vector of set and development level descriptions and a matrix of values
+fn income_matrix(income: &[SetIncome]) -> (Vec<String>, DMatrix<Option<f64>>) {
+    let headings = income
+        .iter()
+        .map(|income| {
+            let level = match (income.set, income.level) {
+                (PropertySet::Station | PropertySet::Utility, owned) => format!("{owned} owned"),
+                (_, 0) => "no houses".to_string(),
+                (_, 1) => "1 house".to_string(),
+                (_, 5) => "hotel".to_string(),
+                (_, houses) => format!("{houses} houses"),
+            };
+
+            format!("{} {level}", income.set)
+        })
+        .collect();
+
+    let mat = DMatrix::from_fn(income.len(), INCOME_HEADINGS.len(), |row, col| {
+        let income = &income[row];
+
+        match col {
+            0 => Some(income.cost as f64),
+            1 => Some(income.per_roll),
+            2 => Some(income.per_turn),
+            3 => Some(income.payback),
+            _ => income.house_roi,
+        }
+    });
+
+    (headings, mat)
+}
+
+/// Calculates the expected number of turns from a start state until first landing on each set which can be bought,
+/// and the probability of first landing on each set during each of a number of turns
+fn calc_set_hitting(map: &TransMatrix, start: &State, turns: usize) -> (Vec<PropertySet>, DMatrix<f64>, DMatrix<f64>) {
+    let boarddef = map.boarddef();
+
+    let mut sets = boarddef
+        .spaces()
+        .iter()
+        .filter(|space| space.price().is_some())
+        .map(|space| space.set())
+        .collect::<Vec<_>>();
+
+    sets.sort();
+    sets.dedup();
+
+    let startidx = map.states()[start];
+
+    let mut expected = DMatrix::zeros(1, sets.len());
+    let mut distribution = DMatrix::zeros(turns, sets.len());
+
+    for (i, set) in sets.iter().enumerate() {
+        let target = |state: &State| boarddef[state.position].set() == *set;
+
+        expected[(0, i)] = map
+            .expected_hitting_turns(target)
+            .map_or(f64::INFINITY, |expected| expected[startidx]);
+
+        distribution.set_column(i, &map.hitting_distribution(start, turns, target).transpose());
+    }
+
+    (sets, expected, distribution)
+}
@@ -1,9 +1,9 @@
 use std::error::Error;
 
 use monopoly_lib::{
-    calc::{probability::Probability, transmatrix::TransMatrix},
+    boarddef::BoardDefinition,
+    calc::{probability::MatrixProbability, transmatrix::TransMatrix},
     movereason::{IntoEnumIterator, MoveReason},
-    space::SPACES,
 };
 use nalgebra::{DMatrix, Matrix};
 use num_traits::NumCast;
@@ -16,8 +16,8 @@ pub fn write_prob_sheet(book: &mut Workbook, name: &str, mat: &TransMatrix, floa
         book,
         name,
         mat.combinedmat(),
-        Some(mat.states().keys()),
-        Some(mat.states().keys()),
+        Some(mat.states_display()),
+        Some(mat.states_display()),
         false,
         float,
     )
@@ -28,8 +28,8 @@ pub fn write_move_sheet(book: &mut Workbook, name: &str, mat: &TransMatrix, floa
         book,
         name,
         mat.movemat(),
-        Some(mat.states().keys()),
-        Some(mat.states().keys()),
+        Some(mat.states_display()),
+        Some(mat.states_display()),
         false,
         float,
     )
@@ -40,8 +40,8 @@ pub fn write_jump_sheet(book: &mut Workbook, name: &str, mat: &TransMatrix, floa
         book,
         name,
         mat.jumpmat(),
-        Some(SPACES.iter()),
-        Some(SPACES.iter()),
+        Some(mat.boarddef().spaces().iter()),
+        Some(mat.boarddef().spaces().iter()),
         false,
         float,
     )
@@ -53,7 +53,7 @@ pub fn write_steady_sheet(book: &mut Workbook, name: &str, mat: &TransMatrix) ->
         name,
         mat.steady(),
         None::<Vec<bool>>,
-        Some(mat.states().keys()),
+        Some(mat.states_display()),
         true,
         |p| *p,
     )
@@ -62,6 +62,7 @@ pub fn write_steady_sheet(book: &mut Workbook, name: &str, mat: &TransMatrix) ->
 pub fn write_reason_sheet<R, C, S>(
     book: &mut Workbook,
     name: &str,
+    boarddef: &BoardDefinition,
     matrix: &Matrix<f64, R, C, S>,
 ) -> Result<(), Box<dyn Error>>
 where
@@ -74,7 +75,7 @@ where
         book,
         name,
         matrix,
-        Some(SPACES.iter()),
+        Some(boarddef.spaces().iter()),
         Some(MoveReason::iter().filter(|m| *m as isize >= 0)),
         false,
         |p| *p,
@@ -99,12 +100,67 @@ where
     Ok(())
 }
 
+pub fn write_turns_sheet<H>(
+    book: &mut Workbook,
+    rowheaders: H,
+    matrix: &DMatrix<f64>,
+    name: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    H: IntoIterator + Clone,
+    H::Item: std::fmt::Display,
+{
+    write_matrix_sheet(
+        book,
+        name,
+        matrix,
+        Some((1..=matrix.nrows()).map(|turn| format!("Turn {turn}"))),
+        Some(rowheaders),
+        true,
+        |p| *p,
+    )?;
+
+    Ok(())
+}
+
+pub fn write_income_sheet<H>(
+    book: &mut Workbook,
+    colheaders: &[&str],
+    rowheaders: H,
+    matrix: &DMatrix<Option<f64>>,
+    name: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    H: IntoIterator + Clone,
+    H::Item: std::fmt::Display,
+{
+    write_matrix_sheet(book, name, matrix, Some(colheaders), Some(rowheaders), false, |p| *p)?;
+
+    Ok(())
+}
+
+pub fn write_columns_sheet<H>(
+    book: &mut Workbook,
+    colheaders: &[&str],
+    rowheaders: H,
+    matrix: &DMatrix<f64>,
+    name: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    H: IntoIterator + Clone,
+    H::Item: std::fmt::Display,
+{
+    write_matrix_sheet(book, name, matrix, Some(colheaders), Some(rowheaders), false, |p| *p)?;
+
+    Ok(())
+}
+
 // Generic matrix to spreadsheet functions
 
 pub fn write_matrix_prob_sheet<RH, CH>(
     book: &mut Workbook,
     name: &str,
-    matrix: &DMatrix<Probability>,
+    matrix: &DMatrix<MatrixProbability>,
     colheaders: Option<CH>,
     rowheaders: Option<RH>,
     transpose: bool,
@@ -0,0 +1,313 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs,
+    ops::Index,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, CardMove, Deck, DeckDefinition};
+use crate::space::{PropertySet, SPACES, Space};
+
+/// Board layout definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardDefinition {
+    /// Name of the board layout
+    name: String,
+    /// Spaces on the board, starting from Go
+    spaces: Vec<Space>,
+    /// Chance card deck
+    #[serde(default = "DeckDefinition::classic_chance")]
+    chance: DeckDefinition,
+    /// Community chest card deck
+    #[serde(default = "DeckDefinition::classic_community_chest")]
+    community_chest: DeckDefinition,
+}
+
+impl Default for BoardDefinition {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl BoardDefinition {
+    /// Create a new board definition, checking the layout and card decks are valid
+    pub fn new(
+        name: &str,
+        spaces: Vec<Space>,
+        chance: DeckDefinition,
+        community_chest: DeckDefinition,
+    ) -> Result<Self, BoardDefinitionError> {
+        let boarddef = Self {
+            name: name.to_string(),
+            spaces,
+            chance,
+            community_chest,
+        };
+
+        boarddef.validate()?;
+
+        Ok(boarddef)
+    }
+
+    /// The classic board layout
+    pub fn classic() -> Self {
+        Self {
+            name: "Classic".to_string(),
+            spaces: SPACES.to_vec(),
+            chance: DeckDefinition::classic_chance(),
+            community_chest: DeckDefinition::classic_community_chest(),
+        }
+    }
+
+    /// Load a board definition from a file. Files with a .json extension are parsed as JSON, all others as TOML
+    pub fn load(file: &Path) -> Result<Self, BoardDefinitionError> {
+        let contents = fs::read_to_string(file).map_err(BoardDefinitionError::Io)?;
+
+        match file.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json(&contents),
+            _ => Self::from_toml(&contents),
+        }
+    }
+
+    /// Parse a board definition from a TOML string
+    pub fn from_toml(toml: &str) -> Result<Self, BoardDefinitionError> {
+        let boarddef: Self = toml::from_str(toml).map_err(BoardDefinitionError::Toml)?;
+
+        boarddef.validate()?;
+
+        Ok(boarddef)
+    }
+
+    /// Parse a board definition from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, BoardDefinitionError> {
+        let boarddef: Self = serde_json::from_str(json).map_err(BoardDefinitionError::Json)?;
+
+        boarddef.validate()?;
+
+        Ok(boarddef)
+    }
+
+    /// Returns the name of the board layout
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns a reference to the spaces on the board
+    pub fn spaces(&self) -> &[Space] {
+        &self.spaces
+    }
+
+    /// Returns the chance card deck definition
+    pub fn chance(&self) -> &DeckDefinition {
+        &self.chance
+    }
+
+    /// Returns the community chest card deck definition
+    pub fn community_chest(&self) -> &DeckDefinition {
+        &self.community_chest
+    }
+
+    /// Returns a card deck definition
+    pub fn deck(&self, deck: Deck) -> &DeckDefinition {
+        match deck {
+            Deck::Chance => &self.chance,
+            Deck::CommunityChest => &self.community_chest,
+        }
+    }
+
+    /// Returns the card deck for a given position if it is a chance or community chest space
+    pub fn deck_type_at(&self, position: usize) -> Option<Deck> {
+        match self.spaces[position] {
+            Space::Chance(_) => Some(Deck::Chance),
+            Space::CommunityChest(_) => Some(Deck::CommunityChest),
+            _ => None,
+        }
+    }
+
+    /// Returns the card deck definition for a given position if it is a chance or community chest space
+    pub fn deck_at(&self, position: usize) -> Option<&DeckDefinition> {
+        self.deck_type_at(position).map(|deck| self.deck(deck))
+    }
+
+    /// Returns the number of spaces on the board
+    pub fn len(&self) -> usize {
+        self.spaces.len()
+    }
+
+    /// Returns true if there are no spaces on the board
+    pub fn is_empty(&self) -> bool {
+        self.spaces.is_empty()
+    }
+
+    /// Returns the position of a space on the board
+    pub fn find(&self, space: Space) -> usize {
+        self.try_find(space)
+            .unwrap_or_else(|| panic!("Space {space:?} not found"))
+    }
+
+    /// Returns the position of a space on the board if it exists
+    pub fn try_find(&self, space: Space) -> Option<usize> {
+        self.spaces.iter().position(|s| *s == space)
+    }
+
+    /// Returns the position of the next railway station from a given position
+    pub fn next_rail(&self, position: usize) -> usize {
+        self.next_in_set(position, PropertySet::Station)
+    }
+
+    /// Returns the position of the next utility from a given position
+    pub fn next_util(&self, position: usize) -> usize {
+        self.next_in_set(position, PropertySet::Utility)
+    }
+
+    /// Returns the position of the next space in a given set from a given position
+    pub fn next_in_set(&self, position: usize, set: PropertySet) -> usize {
+        self.try_next_in_set(position, set)
+            .unwrap_or_else(|| panic!("Next {set} space not found"))
+    }
+
+    /// Returns the position of the next space in a given set from a given position if there is one
+    pub fn try_next_in_set(&self, position: usize, set: PropertySet) -> Option<usize> {
+        self.find_next(position, |s| s.set() == set)
+    }
+
+    /// Returns the position a given number of spaces back from a position
+    pub fn back(&self, position: usize, count: usize) -> usize {
+        (position + self.spaces.len() - (count % self.spaces.len())) % self.spaces.len()
+    }
+
+    fn find_next<F>(&self, position: usize, check: F) -> Option<usize>
+    where
+        F: Fn(&Space) -> bool,
+    {
+        for i in (position + 1)..(position + self.spaces.len()) {
+            let elem = i % self.spaces.len();
+
+            if check(&self.spaces[elem]) {
+                return Some(elem);
+            }
+        }
+
+        None
+    }
+
+    /// Checks the layout can be used by the simulator and calculator
+    fn validate(&self) -> Result<(), BoardDefinitionError> {
+        // Go, jail and go to jail must appear exactly once
+        for required in [Space::Go, Space::Visit, Space::GoToJail] {
+            let count = self.spaces.iter().filter(|s| **s == required).count();
+
+            if count != 1 {
+                return Err(BoardDefinitionError::Invalid(format!(
+                    "{required:?} must appear exactly once (found {count})"
+                )));
+            }
+        }
+
+        // All other spaces must be unique
+        for (i, space) in self.spaces.iter().enumerate() {
+            if self.spaces[..i].contains(space) {
+                return Err(BoardDefinitionError::Invalid(format!(
+                    "{space:?} appears more than once"
+                )));
+            }
+        }
+
+        // Check the card decks
+        self.chance.validate("Chance").map_err(BoardDefinitionError::Invalid)?;
+        self.community_chest
+            .validate("Community chest")
+            .map_err(BoardDefinitionError::Invalid)?;
+
+        // Check all cards can be actioned from every card space and card chains always finish
+        for position in 0..self.spaces.len() {
+            self.validate_cards(position, &mut Vec::new())?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks the cards for a space recursively
+    fn validate_cards(&self, position: usize, chain: &mut Vec<usize>) -> Result<(), BoardDefinitionError> {
+        let Some(deck) = self.deck_at(position) else {
+            return Ok(());
+        };
+
+        let space = self.spaces[position];
+
+        if deck.is_empty() {
+            return Err(BoardDefinitionError::Invalid(format!("No cards for {space:?}")));
+        }
+
+        if chain.contains(&position) {
+            return Err(BoardDefinitionError::Invalid(format!(
+                "Cards can loop forever from {space:?}"
+            )));
+        }
+
+        chain.push(position);
+
+        for entry in deck.cards() {
+            let card = entry.card;
+
+            let valid = match card {
+                Card::GoTo(Space::Visit) => false,
+                Card::GoTo(dest) => self.try_find(dest).is_some(),
+                Card::GoNext(set) => self.try_next_in_set(position, set).is_some(),
+                _ => true,
+            };
+
+            if !valid {
+                return Err(BoardDefinitionError::Invalid(format!(
+                    "Card {card:?} can't be used from {space:?}"
+                )));
+            }
+
+            if let CardMove::To(dest) = card.movement(self, position)
+                && dest != position
+            {
+                self.validate_cards(dest, chain)?;
+            }
+        }
+
+        chain.pop();
+
+        Ok(())
+    }
+}
+
+impl Index<usize> for BoardDefinition {
+    type Output = Space;
+
+    fn index(&self, position: usize) -> &Space {
+        &self.spaces[position]
+    }
+}
+
+/// Board definition load error
+#[derive(Debug)]
+pub enum BoardDefinitionError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl Display for BoardDefinitionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardDefinitionError::Io(e) => write!(f, "Unable to read board definition: {e}"),
+            BoardDefinitionError::Toml(e) => write!(f, "Unable to parse board definition: {e}"),
+            BoardDefinitionError::Json(e) => write!(f, "Unable to parse board definition: {e}"),
+            BoardDefinitionError::Invalid(msg) => write!(f, "Invalid board definition: {msg}"),
+        }
+    }
+}
+
+impl Error for BoardDefinitionError {}
+
+#[cfg(test)]
+mod tests;
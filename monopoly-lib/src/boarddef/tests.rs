@@ -0,0 +1,111 @@
+use super::*;
+use crate::card::DeckEntry;
+
+#[test]
+fn test_classic_file() {
+    let boarddef = BoardDefinition::from_toml(include_str!("../../boards/classic.toml")).unwrap();
+
+    assert_eq!(boarddef, BoardDefinition::default());
+}
+
+#[test]
+fn test_json_roundtrip() {
+    let json = serde_json::to_string(&BoardDefinition::default()).unwrap();
+    let boarddef = BoardDefinition::from_json(&json).unwrap();
+
+    assert_eq!(boarddef, BoardDefinition::default());
+}
+
+#[test]
+fn test_next() {
+    let boarddef = BoardDefinition::default();
+
+    assert_eq!(boarddef.next_rail(boarddef.find(Space::Chance(0))), 15);
+    assert_eq!(boarddef.next_rail(boarddef.find(Space::Chance(2))), 5);
+    assert_eq!(boarddef.next_util(boarddef.find(Space::Chance(1))), 28);
+    assert_eq!(boarddef.next_util(boarddef.find(Space::Chance(2))), 12);
+    assert_eq!(boarddef.back(1, 3), 38);
+}
+
+fn short_board(chance: &[Card]) -> Result<BoardDefinition, BoardDefinitionError> {
+    BoardDefinition::new(
+        "Short",
+        vec![
+            Space::Go,
+            Space::Property(0, 0),
+            Space::Chance(0),
+            Space::Rail(0),
+            Space::Visit,
+            Space::Property(1, 0),
+            Space::Chance(1),
+            Space::Rail(1),
+            Space::GoToJail,
+        ],
+        DeckDefinition::new(
+            chance.iter().map(|&card| DeckEntry { card, count: 1 }).collect(),
+            Some(4),
+        ),
+        DeckDefinition::classic_community_chest(),
+    )
+}
+
+#[test]
+fn test_short_board() {
+    let boarddef = short_board(&[Card::GoNext(PropertySet::Station), Card::Back(2)]).unwrap();
+
+    assert_eq!(boarddef.len(), 9);
+    assert_eq!(boarddef.next_rail(3), 7);
+    assert_eq!(boarddef.next_rail(7), 3);
+    assert_eq!(boarddef.try_find(Space::Utility(0)), None);
+    assert_eq!(boarddef.chance().build_deck().len(), 4);
+}
+
+#[test]
+fn test_invalid_cards() {
+    // No utility on the board
+    assert!(matches!(
+        short_board(&[Card::GoNext(PropertySet::Utility)]),
+        Err(BoardDefinitionError::Invalid(_))
+    ));
+
+    // Space not on the board
+    assert!(matches!(
+        short_board(&[Card::GoTo(Space::Property(7, 1))]),
+        Err(BoardDefinitionError::Invalid(_))
+    ));
+
+    // Chance cards can send the player between chance spaces forever
+    assert!(matches!(
+        short_board(&[Card::GoNext(PropertySet::Chance)]),
+        Err(BoardDefinitionError::Invalid(_))
+    ));
+}
+
+#[test]
+fn test_invalid() {
+    assert!(matches!(
+        BoardDefinition::new(
+            "No jail",
+            vec![Space::Go, Space::Property(0, 0), Space::GoToJail],
+            DeckDefinition::classic_chance(),
+            DeckDefinition::classic_community_chest(),
+        ),
+        Err(BoardDefinitionError::Invalid(_))
+    ));
+
+    assert!(matches!(
+        BoardDefinition::new(
+            "Duplicate",
+            vec![
+                Space::Go,
+                Space::Property(0, 0),
+                Space::Visit,
+                Space::Property(0, 0),
+                Space::GoToJail
+            ],
+            DeckDefinition::classic_chance(),
+            DeckDefinition::classic_community_chest(),
+        ),
+        Err(BoardDefinitionError::Invalid(_))
+    ));
+}
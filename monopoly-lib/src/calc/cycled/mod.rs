@@ -0,0 +1,256 @@
+use std::collections::BTreeMap;
+
+use nalgebra::RowDVector;
+use strum::IntoEnumIterator;
+
+use crate::boarddef::BoardDefinition;
+use crate::card::{Card, CardMove, Deck};
+use crate::rules::Rules;
+use crate::space::Space;
+
+use super::state::State;
+use super::transmatrix::TransMatrix;
+
+/// Maximum number of iterations when calculating the steady state
+const MAX_ITERATIONS: usize = 100_000;
+
+/// Iteration stops when the total change in the state vector is below this
+const TOLERANCE: f64 = 1e-14;
+
+/// Last card drawn from each deck (index into the distinct cards of the deck)
+type Memory = [usize; 2];
+
+/// Position, cards held and last cards drawn after a jump with its probability
+type Jump = (usize, u8, Memory, f64);
+
+impl TransMatrix {
+    /// Calculates the steady state vector when the chance and community chest decks are shuffled once and then
+    /// cycled, with each card drawn put back on the bottom of the deck, instead of each card being drawn
+    /// independently.
+    ///
+    /// Each state is augmented with the last card drawn from each deck. In a randomly shuffled cycled deck the card
+    /// following another is equally likely to be any of the rest of the deck, so the same card can't be drawn twice
+    /// in a row. Longer runs of cards are assumed to be independent. The augmented chain is solved iteratively and
+    /// the result summed over the cards drawn
+    pub fn cycled_steady(&self) -> RowDVector<f64> {
+        let boarddef = self.boarddef();
+        let states = self.states();
+        let decks = Deck::iter()
+            .map(|deck| DeckCards::new(boarddef, deck))
+            .collect::<Vec<_>>();
+
+        let memsize = decks[0].cards.len() * decks[1].cards.len();
+        let memories = (0..decks[0].cards.len())
+            .flat_map(|ch| (0..decks[1].cards.len()).map(move |cc| [ch, cc]))
+            .collect::<Vec<_>>();
+
+        let index = |state: usize, memory: &Memory| state * memsize + memory[0] * decks[1].cards.len() + memory[1];
+
+        // Build the sparse augmented transition matrix from the movement matrix and the cycled deck jumps
+        let statelist = states.keys().collect::<Vec<_>>();
+        let movemat = self.movemat().map(|p| p.as_f64());
+
+        let mut jumps = Jumps {
+            boarddef,
+            rules: self.rules(),
+            decks: &decks,
+            g2j: boarddef.find(Space::GoToJail),
+            cache: BTreeMap::new(),
+        };
+
+        let mut transitions = Vec::with_capacity(states.len() * memsize);
+
+        for i in 0..states.len() {
+            for memory in &memories {
+                let mut row = BTreeMap::new();
+
+                for (j, move_state) in statelist.iter().enumerate() {
+                    let prob = movemat[(i, j)];
+
+                    if prob == 0.0 {
+                        continue;
+                    }
+
+                    for &(position, cards, newmemory, jumpprob) in
+                        jumps.from(move_state.position, move_state.cards, *memory)
+                    {
+                        let doubles = if boarddef[position] == Space::GoToJail {
+                            0
+                        } else {
+                            move_state.doubles
+                        };
+
+                        let new_state = State::new(doubles, position, move_state.jailroll, cards);
+
+                        *row.entry(index(states[&new_state], &newmemory)).or_insert(0.0) += prob * jumpprob;
+                    }
+                }
+
+                transitions.push(row.into_iter().collect::<Vec<_>>());
+            }
+        }
+
+        // Start from the independent draw steady state with the last cards drawn in proportion to the deck
+        let mut current = vec![0.0; transitions.len()];
+
+        for (i, prob) in self.steady().iter().enumerate() {
+            for memory in &memories {
+                current[index(i, memory)] = prob * decks[0].proportion(memory[0]) * decks[1].proportion(memory[1]);
+            }
+        }
+
+        // Power iteration
+        for _ in 0..MAX_ITERATIONS {
+            let mut next = vec![0.0; current.len()];
+
+            for (prob, row) in current.iter().zip(&transitions) {
+                if *prob != 0.0 {
+                    for (j, p) in row {
+                        next[*j] += prob * p;
+                    }
+                }
+            }
+
+            let change = current.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum::<f64>();
+
+            current = next;
+
+            if change < TOLERANCE {
+                break;
+            }
+        }
+
+        // Sum over the last cards drawn
+        RowDVector::from_iterator(
+            states.len(),
+            current.chunks(memsize).map(|chunk| chunk.iter().sum::<f64>()),
+        )
+    }
+}
+
+/// Distinct cards in a deck with the number of copies of each
+struct DeckCards {
+    deck: Deck,
+    cards: Vec<(Card, usize)>,
+}
+
+impl DeckCards {
+    fn new(boarddef: &BoardDefinition, deck: Deck) -> Self {
+        let mut cards: Vec<(Card, usize)> = Vec::new();
+
+        for card in boarddef.deck(deck).build_deck() {
+            match cards.iter_mut().find(|(c, _)| *c == card) {
+                Some((_, count)) => *count += 1,
+                None => cards.push((card, 1)),
+            }
+        }
+
+        if cards.is_empty() {
+            // Deck is never drawn from
+            cards.push((Card::Inconsequential, 1));
+        }
+
+        Self { deck, cards }
+    }
+
+    /// Returns the proportion of the deck made up of a card
+    fn proportion(&self, card: usize) -> f64 {
+        self.cards[card].1 as f64 / self.cards.iter().map(|(_, count)| count).sum::<usize>() as f64
+    }
+
+    /// Returns the probability of drawing each card after the last card drawn
+    fn next(&self, last: usize, held: u8) -> Vec<f64> {
+        // A held get out of jail free card isn't in the deck
+        let counts = self
+            .cards
+            .iter()
+            .map(|(card, count)| {
+                if *card == Card::GetOutOfJail && held & self.deck.bit() != 0 {
+                    0
+                } else {
+                    *count
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let total = counts.iter().sum::<usize>();
+
+        // The last card is now at the bottom of the deck so can't be drawn next
+        let exclude = counts[last] > 0 && total > 1;
+
+        counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let count = count - (exclude && i == last) as usize;
+
+                count as f64 / (total - exclude as usize) as f64
+            })
+            .collect()
+    }
+}
+
+/// Calculates jumps from a space with the last cards drawn
+struct Jumps<'a> {
+    boarddef: &'a BoardDefinition,
+    rules: &'a Rules,
+    decks: &'a [DeckCards],
+    g2j: usize,
+    /// Jumps from a position, cards held and last cards drawn to a position, cards held and last cards drawn with
+    /// probability
+    cache: BTreeMap<(usize, u8, Memory), Vec<Jump>>,
+}
+
+impl Jumps<'_> {
+    fn from(&mut self, position: usize, cards: u8, memory: Memory) -> &[Jump] {
+        if !self.cache.contains_key(&(position, cards, memory)) {
+            let jumps = self.calc(position, cards, memory);
+
+            self.cache.insert((position, cards, memory), jumps);
+        }
+
+        &self.cache[&(position, cards, memory)]
+    }
+
+    fn calc(&mut self, position: usize, cards: u8, memory: Memory) -> Vec<Jump> {
+        let Some(deck) = self.boarddef.deck_type_at(position) else {
+            return vec![(position, cards, memory, 1.0)];
+        };
+
+        let decks = self.decks;
+        let deckcards = &decks[deck as usize];
+        let mut jumps = Vec::new();
+
+        for (card, prob) in deckcards.next(memory[deck as usize], cards).into_iter().enumerate() {
+            if prob == 0.0 {
+                continue;
+            }
+
+            let mut newmemory = memory;
+            newmemory[deck as usize] = card;
+
+            let (newpos, newcards) = match deckcards.cards[card].0 {
+                Card::GetOutOfJail => (position, cards | deck.bit()), // Keep the card
+                other => match self.rules.card_movement(other, self.boarddef, position) {
+                    CardMove::To(elem) => (elem, cards),
+                    CardMove::Jail => (self.g2j, cards),
+                    CardMove::Stay => (position, cards),
+                },
+            };
+
+            if newpos == position {
+                jumps.push((position, newcards, newmemory, prob));
+            } else {
+                // Moved to another space which may jump again
+                for &(pos, c, m, p) in self.from(newpos, newcards, newmemory) {
+                    jumps.push((pos, c, m, prob * p));
+                }
+            }
+        }
+
+        jumps
+    }
+}
+
+#[cfg(test)]
+mod tests;
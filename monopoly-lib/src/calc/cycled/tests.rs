@@ -0,0 +1,79 @@
+use super::*;
+use crate::card::{DeckDefinition, DeckEntry};
+use crate::dice::DiceModel;
+use crate::rules::BackPastGo;
+use crate::strategy::{JailWait, PayJail};
+
+#[test]
+fn test_inconsequential_decks() {
+    // Decks which don't move the player give the same result whether cycled or not
+    let boarddef = BoardDefinition::new(
+        "Inconsequential",
+        BoardDefinition::default().spaces().to_vec(),
+        DeckDefinition::new(vec![], Some(16)),
+        DeckDefinition::new(vec![], Some(16)),
+    )
+    .unwrap();
+
+    let mat = TransMatrix::new(boarddef, &JailWait, false);
+
+    let cycled = mat.cycled_steady();
+
+    assert!((cycled - mat.steady()).abs().max() < 1e-12);
+}
+
+#[test]
+fn test_cycled_rules() {
+    // Short board with a deck of identical cards moving back past go, which gives the same result whether
+    // cycled or not
+    let boarddef = BoardDefinition::new(
+        "Short",
+        vec![
+            Space::Go,
+            Space::Property(0, 0),
+            Space::Chance(0),
+            Space::Rail(0),
+            Space::Visit,
+            Space::Property(1, 0),
+            Space::Chance(1),
+            Space::Rail(1),
+            Space::GoToJail,
+        ],
+        DeckDefinition::new(
+            vec![DeckEntry {
+                card: Card::Back(3),
+                count: 16,
+            }],
+            Some(16),
+        ),
+        DeckDefinition::new(vec![], Some(16)),
+    )
+    .unwrap();
+
+    let rules = Rules {
+        back_past_go: BackPastGo::Stop,
+        ..Rules::classic()
+    };
+
+    let mat = TransMatrix::with_rules(boarddef, &JailWait, DiceModel::classic(), rules, false);
+
+    let cycled = mat.cycled_steady();
+
+    assert!((cycled - mat.steady()).abs().max() < 1e-12);
+}
+
+#[test]
+fn test_cycled() {
+    let boarddef = BoardDefinition::default();
+    let mat = TransMatrix::new(boarddef.clone(), &PayJail, false);
+
+    let cycled = mat.cycled_steady();
+
+    assert!((cycled.sum() - 1.0).abs() < 1e-12);
+
+    // Differences are small but not zero
+    let diff = (&cycled - mat.steady()).abs().max();
+
+    assert!(diff > 1e-6);
+    assert!(diff < 1e-3);
+}
@@ -0,0 +1,223 @@
+use nalgebra::{DMatrix, RowDVector};
+use rand::{RngCore, SeedableRng};
+use strum::IntoEnumIterator;
+
+use crate::movereason::MoveReason;
+use crate::sim::Board;
+
+use super::transmatrix::TransMatrix;
+
+/// Maximum number of iterations when evaluating the incomplete gamma function
+const GAMMA_ITERATIONS: usize = 1000;
+
+/// Relative accuracy of the incomplete gamma function
+const GAMMA_EPSILON: f64 = 1e-15;
+
+/// Comparison of simulated arrivals with the probabilities calculated from the transition matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoodnessOfFit {
+    /// Number of moves simulated
+    pub moves: u64,
+    /// Calculated probability of arriving on each space per move
+    pub expected: RowDVector<f64>,
+    /// Simulated frequency of arriving on each space per move
+    pub observed: RowDVector<f64>,
+    /// Simulated frequency minus calculated probability for each space
+    pub deviation: RowDVector<f64>,
+    /// Pearson chi-square statistic of the arrival counts
+    pub chi_square: f64,
+    /// Degrees of freedom of the chi-square statistic (spaces which can be arrived on less one)
+    pub degrees_of_freedom: usize,
+    /// Probability of a chi-square statistic at least this large if the simulation matches the calculation
+    pub p_value: f64,
+    /// Simulated frequency minus calculated probability of arriving on each space (columns) for each move reason
+    /// (rows, indexed by `MoveReason as usize`)
+    pub reason_deviation: DMatrix<f64>,
+}
+
+impl GoodnessOfFit {
+    /// Returns the total simulated frequency minus calculated probability of arriving anywhere for a move reason
+    pub fn reason_total_deviation(&self, reason: MoveReason) -> Option<f64> {
+        let row = reason as isize;
+
+        (row >= 0).then(|| self.reason_deviation.row(row as usize).sum())
+    }
+
+    /// Returns the largest absolute deviation over all spaces
+    pub fn max_deviation(&self) -> f64 {
+        self.deviation.amax()
+    }
+}
+
+impl TransMatrix {
+    /// Compares the arrivals counted by a simulation with the steady state probabilities. The board and the
+    /// simulation should use the same board definition and jail strategy, and draw cards randomly. Successive moves
+    /// in a simulation aren't independent so the p-value is a guide rather than an exact test
+    pub fn goodness_of_fit<R: RngCore + SeedableRng>(&self, board: &Board<R>) -> GoodnessOfFit {
+        let spaces = self.boarddef().len();
+
+        assert_eq!(board.arrivals().len(), spaces, "Board layouts differ");
+
+        let moves = board.moves();
+
+        // Calculated probability of arriving on each space
+        let mut expected = RowDVector::zeros(spaces);
+
+        for (state, prob) in self.states().keys().zip(self.steady().iter()) {
+            expected[state.position] += prob;
+        }
+
+        // Simulated frequencies
+        let frequency = |count: u64| if moves == 0 { 0.0 } else { count as f64 / moves as f64 };
+
+        let observed = RowDVector::from_iterator(spaces, board.arrivals().iter().map(|count| frequency(*count)));
+
+        // Pearson chi-square over the spaces which can be arrived on
+        let mut chi_square = 0.0;
+        let mut categories = 0;
+
+        for (elem, count) in board.arrivals().iter().enumerate() {
+            let expected_count: f64 = expected[elem] * moves as f64;
+
+            if expected_count > 0.0 {
+                chi_square += (*count as f64 - expected_count).powi(2) / expected_count;
+                categories += 1;
+            }
+        }
+
+        let degrees_of_freedom = categories.max(1) - 1;
+
+        // Deviation of each move reason (rolled arrivals aren't counted by reason)
+        let reason_expected = self.calc_movereason_probabilty();
+
+        let reason_deviation = DMatrix::from_fn(MoveReason::uint_count(), spaces, |reason, elem| {
+            frequency(board.arrival_reasons()[elem][reason]) - reason_expected[(reason, elem)]
+        });
+
+        GoodnessOfFit {
+            moves,
+            deviation: &observed - &expected,
+            expected,
+            observed,
+            chi_square,
+            degrees_of_freedom,
+            p_value: chi_square_p_value(chi_square, degrees_of_freedom),
+            reason_deviation,
+        }
+    }
+}
+
+impl std::fmt::Display for GoodnessOfFit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Moves {}, chi-square {:.2} ({} degrees of freedom), p-value {:.4}, max deviation {:.6}",
+            self.moves,
+            self.chi_square,
+            self.degrees_of_freedom,
+            self.p_value,
+            self.max_deviation()
+        )?;
+
+        for reason in MoveReason::iter() {
+            if let Some(deviation) = self.reason_total_deviation(reason) {
+                writeln!(f, "  {reason}: {deviation:+.6}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the probability of a chi-square statistic at least as large as the one given
+fn chi_square_p_value(chi_square: f64, degrees_of_freedom: usize) -> f64 {
+    if degrees_of_freedom == 0 {
+        return 1.0;
+    }
+
+    gamma_q(degrees_of_freedom as f64 / 2.0, chi_square / 2.0)
+}
+
+/// Calculates the regularised upper incomplete gamma function Q(a, x)
+fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    let prefix = (a * x.ln() - x - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        // Series expansion of P(a, x)
+        let mut term = 1.0 / a;
+        let mut sum = term;
+
+        for n in 1..GAMMA_ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+
+            if term.abs() < sum.abs() * GAMMA_EPSILON {
+                break;
+            }
+        }
+
+        (1.0 - sum * prefix).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction for Q(a, x) using the modified Lentz method
+        let tiny = f64::MIN_POSITIVE / GAMMA_EPSILON;
+
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+
+        for n in 1..GAMMA_ITERATIONS {
+            let an = -(n as f64) * (n as f64 - a);
+            b += 2.0;
+
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+
+            if (delta - 1.0).abs() < GAMMA_EPSILON {
+                break;
+            }
+        }
+
+        (h * prefix).clamp(0.0, 1.0)
+    }
+}
+
+/// Calculates ln Γ(x) for positive x using the Lanczos approximation
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .fold(1.000_000_000_190_015, |acc, (i, c)| acc + c / (x + 1.0 + i as f64));
+
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+#[cfg(test)]
+mod tests;
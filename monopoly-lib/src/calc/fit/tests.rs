@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use super::*;
+use crate::boarddef::BoardDefinition;
+use crate::dice::{DiceModel, Die};
+use crate::rules::Rules;
+use crate::sim::SimRng;
+use crate::strategy::{JailWait, PlayerStrategy};
+
+#[test]
+fn test_chi_square_p_value() {
+    // Known values of the chi-square distribution
+    assert!((chi_square_p_value(3.841_459, 1) - 0.05).abs() < 1e-6);
+    assert!((chi_square_p_value(2.0, 2) - (-1.0f64).exp()).abs() < 1e-9);
+    assert!((chi_square_p_value(18.307_038, 10) - 0.05).abs() < 1e-6);
+    assert!((chi_square_p_value(63.690_740, 40) - 0.01).abs() < 1e-6);
+    assert_eq!(chi_square_p_value(0.0, 5), 1.0);
+    assert_eq!(chi_square_p_value(10.0, 0), 1.0);
+}
+
+#[test]
+fn test_goodness_of_fit() {
+    let boarddef = BoardDefinition::default();
+    let mat = TransMatrix::new(boarddef.clone(), &JailWait, false);
+
+    let mut board: Board<SimRng> = Board::with_seed(
+        boarddef,
+        vec![Arc::new(JailWait) as Arc<dyn PlayerStrategy>],
+        true,
+        1234,
+    );
+
+    for _ in 0..200_000 {
+        board.turn();
+    }
+
+    let fit = mat.goodness_of_fit(&board);
+
+    assert_eq!(fit.moves, board.moves());
+    assert!((fit.observed.sum() - 1.0).abs() < 1e-9);
+    assert!(fit.deviation.sum().abs() < 1e-9);
+
+    // Arrivals in jail are counted on the go to jail space so every space can be arrived on
+    assert_eq!(fit.degrees_of_freedom, 39);
+
+    // The simulation agrees with the calculation
+    assert!(fit.p_value > 0.001, "{fit}");
+    assert!(fit.max_deviation() < 0.002, "{fit}");
+
+    for reason in MoveReason::iter() {
+        match fit.reason_total_deviation(reason) {
+            Some(deviation) => assert!(deviation.abs() < 0.002, "{fit}"),
+            None => assert_eq!(reason, MoveReason::Roll),
+        }
+    }
+}
+
+#[test]
+fn test_dice_models_fit() {
+    let boarddef = BoardDefinition::default();
+    let die = Die::loaded(vec![2, 1, 1, 1, 1, 2]);
+
+    for dice in [
+        DiceModel::new(vec![die.clone(), die], None).unwrap(),
+        DiceModel::new(vec![Die::fair(4), Die::fair(4), Die::fair(4)], None).unwrap(),
+        DiceModel::mega(),
+    ] {
+        let mat = TransMatrix::with_dice(boarddef.clone(), &JailWait, dice.clone(), false);
+
+        let mut board: Board<SimRng> = Board::with_seed(
+            boarddef.clone(),
+            vec![Arc::new(JailWait) as Arc<dyn PlayerStrategy>],
+            true,
+            1234,
+        );
+
+        board.set_dice(dice);
+
+        for _ in 0..100_000 {
+            board.turn();
+        }
+
+        // The simulation agrees with the calculation for the same dice
+        let fit = mat.goodness_of_fit(&board);
+
+        assert!(fit.p_value > 0.001, "{fit}");
+    }
+}
+
+#[test]
+fn test_rules_fit() {
+    let boarddef = BoardDefinition::default();
+
+    let rules = Rules {
+        triple_double_jail: false,
+        jail_attempts: 1,
+        ..Rules::classic()
+    };
+
+    let mat = TransMatrix::with_rules(boarddef.clone(), &JailWait, DiceModel::classic(), rules, false);
+
+    let mut board: Board<SimRng> = Board::with_seed(
+        boarddef,
+        vec![Arc::new(JailWait) as Arc<dyn PlayerStrategy>],
+        true,
+        1234,
+    );
+
+    board.set_rules(rules);
+
+    for _ in 0..100_000 {
+        board.turn();
+    }
+
+    // The simulation agrees with the calculation under the same rules
+    let fit = mat.goodness_of_fit(&board);
+
+    assert!(fit.p_value > 0.001, "{fit}");
+}
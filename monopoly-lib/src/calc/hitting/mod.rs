@@ -0,0 +1,94 @@
+use nalgebra::{DMatrix, DVector, RowDVector};
+
+use super::state::State;
+use super::transmatrix::TransMatrix;
+
+impl TransMatrix {
+    /// Calculates the expected number of turns until first landing in one of a set of target states, from the start
+    /// of a turn in each state. The turn being started counts, so a state landed on every turn has an expected
+    /// hitting time of 1. Starting in a target state gives the expected time until it is landed on again. Returns
+    /// None if a target state can't be reached from every state
+    pub fn expected_hitting_turns<F>(&self, target: F) -> Option<RowDVector<f64>>
+    where
+        F: Fn(&State) -> bool,
+    {
+        let dim = self.states().len();
+        let combinedmat = self.combinedmat().map(|p| p.as_f64());
+        let istarget = self.states().keys().map(&target).collect::<Vec<_>>();
+
+        // Find the states from which a target state can be reached
+        let mut reaches = vec![false; dim];
+
+        loop {
+            let mut changed = false;
+
+            for i in 0..dim {
+                if !reaches[i] && (0..dim).any(|j| (istarget[j] || reaches[j]) && combinedmat[(i, j)] != 0.0) {
+                    reaches[i] = true;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        if reaches.contains(&false) {
+            return None;
+        }
+
+        // Expected turns from a state satisfy f(s) = Σ P(s, s')·g(s') where g(s') is 1 for a target state, f(s') if
+        // another move is made in the same turn, or 1 + f(s') if the turn ends. Rearrange to (I - Q)·f = b where Q
+        // is the transition matrix to states which aren't targets
+        let mut a = DMatrix::identity(dim, dim);
+        let mut b = DVector::zeros(dim);
+
+        for (state, &j) in self.states() {
+            for i in 0..dim {
+                let p = combinedmat[(i, j)];
+
+                if !istarget[j] {
+                    a[(i, j)] -= p;
+                }
+
+                if istarget[j] || state.doubles == 0 {
+                    b[i] += p;
+                }
+            }
+        }
+
+        a.lu().solve(&b).map(|turns| turns.transpose())
+    }
+
+    /// Calculates the probability of first landing in one of a set of target states during each of the first `turns`
+    /// turns, starting from a given state
+    pub fn hitting_distribution<F>(&self, start: &State, turns: usize, target: F) -> RowDVector<f64>
+    where
+        F: Fn(&State) -> bool,
+    {
+        let combinedmat = self.combinedmat().map(|p| p.as_f64());
+        let istarget = self.states().keys().map(target).collect::<Vec<_>>();
+
+        let mut distribution = RowDVector::zeros(turns);
+
+        // Probability of not having landed in a target state yet, for each state
+        let mut current = self.start_vector(start);
+
+        for turn in 0..turns {
+            current = self.play_turn(&combinedmat, current, |i, p| {
+                if istarget[i] {
+                    distribution[turn] += p;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        distribution
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+use crate::boarddef::BoardDefinition;
+use crate::space::{PropertySet, Space};
+use crate::strategy::{JailWait, PayJail};
+
+#[test]
+fn test_hitting_turns() {
+    let boarddef = BoardDefinition::default();
+    let mat = TransMatrix::new(boarddef.clone(), &PayJail, false);
+    let start = State::start(&boarddef);
+
+    // Something is always landed on in the first turn
+    let always = mat.expected_hitting_turns(|_| true).unwrap();
+
+    assert!(always.iter().all(|t| (t - 1.0).abs() < 1e-9));
+
+    // Expected turns matches the mean of the distribution
+    let orange = |state: &State| boarddef[state.position].set() == PropertySet::Orange;
+
+    let turns = mat.expected_hitting_turns(orange).unwrap();
+    let distribution = mat.hitting_distribution(&start, 1000, orange);
+
+    let mean = distribution
+        .iter()
+        .enumerate()
+        .map(|(turn, p)| (turn + 1) as f64 * p)
+        .sum::<f64>();
+
+    assert!((distribution.sum() - 1.0).abs() < 1e-9);
+    assert!((turns[mat.states()[&start]] - mean).abs() < 1e-6);
+
+    // Can't reach the oranges in one turn without a double or a card
+    assert!(distribution[0] > 0.0);
+    assert!(distribution[0] < distribution[1]);
+}
+
+#[test]
+fn test_hitting_unreachable() {
+    let boarddef = BoardDefinition::default();
+    let mat = TransMatrix::new(boarddef.clone(), &JailWait, false);
+
+    // A state which doesn't exist can't be hit
+    assert!(mat.expected_hitting_turns(|_| false).is_none());
+
+    // The go to jail space is reached from everywhere
+    let g2j = boarddef.find(Space::GoToJail);
+
+    assert!(
+        mat.expected_hitting_turns(|state| state.position == g2j && state.jailroll == 0)
+            .is_some()
+    );
+}
@@ -0,0 +1,168 @@
+use nalgebra::RowDVector;
+
+use crate::space::{PropertySet, Rent};
+
+use super::transmatrix::TransMatrix;
+
+/// Expected rent income from owning spaces in a set at a development level
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetIncome {
+    /// The set owned
+    pub set: PropertySet,
+    /// Houses on each street in a complete set (0 is undeveloped, 5 is a hotel), or number of stations or utilities
+    /// owned
+    pub level: u8,
+    /// Total cost of the spaces and buildings
+    pub cost: u32,
+    /// Expected rent received per opponent roll
+    pub per_roll: f64,
+    /// Expected rent received per opponent turn
+    pub per_turn: f64,
+    /// Number of opponent turns for the rent to pay back the cost
+    pub payback: f64,
+    /// Extra rent per opponent turn for each unit of money spent on the last house built on each street
+    pub house_roi: Option<f64>,
+}
+
+impl TransMatrix {
+    /// Calculates the expected rent income for each set which can be bought at each development level. Stations and
+    /// utilities are assumed to be the ones landed on most
+    pub fn income(&self) -> Vec<SetIncome> {
+        let boarddef = self.boarddef();
+
+        let mut sets = boarddef
+            .spaces()
+            .iter()
+            .filter(|space| space.rent().is_some())
+            .map(|space| space.set())
+            .collect::<Vec<_>>();
+
+        sets.sort();
+        sets.dedup();
+
+        sets.into_iter().flat_map(|set| self.set_income(set)).collect()
+    }
+
+    /// Calculates the expected rent income for a set at each development level
+    pub fn set_income(&self, set: PropertySet) -> Vec<SetIncome> {
+        let boarddef = self.boarddef();
+        let moves = self.moves_per_turn();
+
+        // Probability of landing on each space per roll, and the expected dice total rolled to land there
+        let landing = self.landing_probability();
+        let dice = self.landing_dice();
+
+        // Find the spaces in the set, landed on most first
+        let mut spaces = (0..boarddef.len())
+            .filter(|&elem| boarddef[elem].set() == set && boarddef[elem].rent().is_some())
+            .collect::<Vec<_>>();
+
+        spaces.sort_by(|a, b| landing[*b].total_cmp(&landing[*a]));
+
+        let price = |spaces: &[usize]| spaces.iter().filter_map(|elem| boarddef[*elem].price()).sum::<u32>();
+
+        let income = |level: u8, cost: u32, per_roll: f64, last: Option<&SetIncome>| {
+            let per_turn = per_roll * moves;
+
+            let house_roi = last.map(|last| (per_turn - last.per_turn) / (cost - last.cost) as f64);
+
+            SetIncome {
+                set,
+                level,
+                cost,
+                per_roll,
+                per_turn,
+                payback: cost as f64 / per_turn,
+                house_roi,
+            }
+        };
+
+        let mut levels: Vec<SetIncome> = Vec::new();
+
+        match spaces.first().and_then(|elem| boarddef[*elem].rent()) {
+            Some(Rent::Property(_)) => {
+                let house_cost = spaces
+                    .iter()
+                    .filter_map(|elem| boarddef[*elem].house_cost())
+                    .sum::<u32>();
+
+                for level in 0..6 {
+                    let per_roll = spaces
+                        .iter()
+                        .map(|&elem| {
+                            let Some(Rent::Property(rent)) = boarddef[elem].rent() else {
+                                unreachable!()
+                            };
+
+                            // Rent is doubled on undeveloped streets when the whole set is owned
+                            let rent = if level == 0 { rent[0] * 2 } else { rent[level as usize] };
+
+                            landing[elem] * rent as f64
+                        })
+                        .sum();
+
+                    let cost = price(&spaces) + house_cost * level as u32;
+
+                    levels.push(income(level, cost, per_roll, levels.last()));
+                }
+            }
+            Some(Rent::Station(rent)) => {
+                for owned in 1..=spaces.len().min(rent.len()) {
+                    let per_roll = spaces[..owned]
+                        .iter()
+                        .map(|&elem| landing[elem] * rent[owned - 1] as f64)
+                        .sum();
+
+                    levels.push(income(owned as u8, price(&spaces[..owned]), per_roll, None));
+                }
+            }
+            Some(Rent::Utility(mult)) => {
+                for owned in 1..=spaces.len().min(mult.len()) {
+                    // Rent is a multiple of the dice total rolled to land on the utility
+                    let per_roll = spaces[..owned]
+                        .iter()
+                        .map(|&elem| dice[elem] * mult[owned - 1] as f64)
+                        .sum();
+
+                    levels.push(income(owned as u8, price(&spaces[..owned]), per_roll, None));
+                }
+            }
+            None => (),
+        }
+
+        levels
+    }
+
+    /// Returns the probability of landing on each space per roll in the steady state. Staying in jail doesn't count
+    fn landing_probability(&self) -> RowDVector<f64> {
+        let mut landing = RowDVector::zeros(self.boarddef().len());
+
+        for (state, prob) in self.states().keys().zip(self.steady().iter()) {
+            if state.jailroll == 0 {
+                landing[state.position] += prob;
+            }
+        }
+
+        landing
+    }
+
+    /// Returns the expected dice total rolled multiplied by the probability of landing on each space per roll in the
+    /// steady state
+    fn landing_dice(&self) -> RowDVector<f64> {
+        let dicemat = self.dicemat().map(|p| p.as_f64());
+        let dice = self.steady() * dicemat;
+
+        let mut landing = RowDVector::zeros(self.boarddef().len());
+
+        for (state, value) in self.states().keys().zip(dice.iter()) {
+            if state.jailroll == 0 {
+                landing[state.position] += value;
+            }
+        }
+
+        landing
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+use super::*;
+use crate::boarddef::BoardDefinition;
+use crate::strategy::JailWait;
+
+#[test]
+fn test_income() {
+    let boarddef = BoardDefinition::default();
+    let mat = TransMatrix::new(boarddef.clone(), &JailWait, false);
+
+    let income = mat.income();
+
+    // 8 street sets with 6 levels, 4 stations and 2 utilities
+    assert_eq!(income.len(), 8 * 6 + 4 + 2);
+
+    let orange = mat.set_income(PropertySet::Orange);
+
+    assert_eq!(orange.len(), 6);
+    assert_eq!(orange[0].cost, 180 + 180 + 200);
+    assert_eq!(orange[5].cost, 180 + 180 + 200 + 5 * 3 * 100);
+    assert!(orange[0].house_roi.is_none());
+
+    for (last, level) in orange.iter().zip(&orange[1..]) {
+        assert!(level.per_turn > last.per_turn);
+        assert!(level.per_turn > level.per_roll);
+        assert!((level.payback - level.cost as f64 / level.per_turn).abs() < 1e-9);
+        assert!(level.house_roi.unwrap() > 0.0);
+    }
+
+    // The third house is the best investment
+    let best = orange
+        .iter()
+        .max_by(|a, b| a.house_roi.unwrap_or(0.0).total_cmp(&b.house_roi.unwrap_or(0.0)))
+        .unwrap();
+
+    assert_eq!(best.level, 3);
+
+    // Utility rent uses the dice roll. Rolls of 7 are most likely so the average roll is near that
+    let utility = mat.set_income(PropertySet::Utility);
+    let landing = mat.landing_probability();
+    let dice = mat.landing_dice();
+
+    for elem in (0..boarddef.len()).filter(|&elem| boarddef[elem].set() == PropertySet::Utility) {
+        let average = dice[elem] / landing[elem];
+
+        assert!(average > 6.0 && average < 9.0);
+    }
+
+    assert_eq!(utility[1].cost, 300);
+    assert!(utility[1].per_roll > utility[0].per_roll * 2.0);
+}
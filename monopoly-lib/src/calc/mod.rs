@@ -1,4 +1,8 @@
-mod dice;
+pub mod cycled;
+pub mod fit;
+pub mod hitting;
+pub mod income;
 pub mod probability;
 pub mod state;
+pub mod steady;
 pub mod transmatrix;
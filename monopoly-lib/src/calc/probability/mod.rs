@@ -1,10 +1,14 @@
 use std::{
-    fmt::Display,
+    error::Error,
+    fmt::{Debug, Display},
     iter::Sum,
     ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign},
 };
 
-use num_traits::{NumCast, One, PrimInt, Zero};
+use num_bigint::{BigInt, ToBigInt};
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, FromPrimitive, NumCast, One, PrimInt, Signed, ToPrimitive, Zero};
 
 macro_rules! p {
     ($numerator:literal/$denominator:literal) => {
@@ -16,10 +20,106 @@ macro_rules! p {
 }
 pub(crate) use p;
 
+/// Integer type used for the numerator and denominator of a probability
+pub trait ProbabilityInt:
+    Integer
+    + Signed
+    + Clone
+    + Debug
+    + Display
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + FromPrimitive
+    + ToPrimitive
+    + ToBigInt
+    + 'static
+{
+    /// Integer type with a larger range used when solving equations with probabilities as coefficients
+    type Wide: ProbabilityInt;
+
+    /// Converts a fraction to floating point
+    fn ratio_f64(numerator: &Self, denominator: &Self) -> f64;
+
+    /// Converts to the wider integer type
+    fn widen(&self) -> Self::Wide;
+}
+
+impl ProbabilityInt for i64 {
+    type Wide = i128;
+
+    fn ratio_f64(numerator: &Self, denominator: &Self) -> f64 {
+        *numerator as f64 / *denominator as f64
+    }
+
+    fn widen(&self) -> i128 {
+        *self as i128
+    }
+}
+
+impl ProbabilityInt for i128 {
+    type Wide = i128;
+
+    fn ratio_f64(numerator: &Self, denominator: &Self) -> f64 {
+        *numerator as f64 / *denominator as f64
+    }
+
+    fn widen(&self) -> i128 {
+        *self
+    }
+}
+
+impl ProbabilityInt for BigInt {
+    type Wide = BigInt;
+
+    fn ratio_f64(numerator: &Self, denominator: &Self) -> f64 {
+        BigRational::new(numerator.clone(), denominator.clone())
+            .to_f64()
+            .unwrap_or(f64::NAN)
+    }
+
+    fn widen(&self) -> BigInt {
+        self.clone()
+    }
+}
+
+/// Probability with arbitrary precision numerator and denominator which can't overflow
+pub type BigProbability = Probability<BigInt>;
+
+/// Probability used for the entries of the transition matrices. Uses `i64` unless the `bigint` feature is enabled
+#[cfg(not(feature = "bigint"))]
+pub type MatrixProbability = Probability;
+
+/// Probability used for the entries of the transition matrices. Uses `BigInt` as the `bigint` feature is enabled
+#[cfg(feature = "bigint")]
+pub type MatrixProbability = BigProbability;
+
+/// Error from probability arithmetic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbabilityError {
+    /// The numerator or denominator is too large for the integer type
+    Overflow,
+    /// Division by a zero probability
+    DivideByZero,
+}
+
+impl Display for ProbabilityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProbabilityError::Overflow => write!(f, "Probability overflowed"),
+            ProbabilityError::DivideByZero => write!(f, "Probability divided by zero"),
+        }
+    }
+}
+
+impl Error for ProbabilityError {}
+
+/// Exact probability stored as a fraction. The integer type defaults to `i64`, use `BigProbability` for arbitrary
+/// precision integers
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Probability {
-    numerator: i64,
-    denominator: u64,
+pub struct Probability<I = i64> {
+    numerator: I,
+    denominator: I,
 }
 
 impl Probability {
@@ -34,38 +134,138 @@ impl Probability {
     };
 
     pub const fn new(numerator: i64, denominator: u64) -> Self {
+        assert!(denominator != 0, "Probability denominator is zero");
+        assert!(denominator <= i64::MAX as u64, "Probability overflowed");
+
+        let gcd = gcd(numerator.unsigned_abs(), denominator);
+
+        Self {
+            numerator: numerator / gcd as i64,
+            denominator: (denominator / gcd) as i64,
+        }
+    }
+}
+
+impl<I: ProbabilityInt> Probability<I> {
+    /// Creates a probability from a numerator and denominator
+    pub fn from_parts(numerator: I, denominator: I) -> Result<Self, ProbabilityError> {
+        if denominator.is_zero() {
+            return Err(ProbabilityError::DivideByZero);
+        }
+
         let mut p = Self { numerator, denominator };
 
         p.normalise();
 
-        p
+        Ok(p)
     }
 
-    pub const fn reciprocal(&self) -> Self {
-        let (numerator, denominator) = if self.numerator < 0 {
-            (-(self.denominator as i64), (-self.numerator as u64))
-        } else {
-            (self.denominator as i64, self.numerator as u64)
-        };
+    /// Converts a probability with `i64` numerator and denominator to this integer type
+    pub fn from_probability(p: Probability) -> Self {
+        Self {
+            numerator: Self::integer(p.numerator),
+            denominator: Self::integer(p.denominator),
+        }
+    }
+
+    /// Returns the numerator (carries the sign)
+    pub fn numerator(&self) -> &I {
+        &self.numerator
+    }
+
+    /// Returns the denominator (always positive)
+    pub fn denominator(&self) -> &I {
+        &self.denominator
+    }
+
+    pub fn reciprocal(&self) -> Self {
+        self.checked_reciprocal()
+            .unwrap_or_else(|err| panic!("{err} calculating reciprocal of {self}"))
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        I::ratio_f64(&self.numerator, &self.denominator)
+    }
 
-        Self { numerator, denominator }
+    /// Adds two probabilities, returning an error on overflow
+    pub fn checked_add(&self, other: &Self) -> Result<Self, ProbabilityError> {
+        self.checked_combine(other, I::checked_add)
     }
 
-    pub const fn as_f64(&self) -> f64 {
-        self.numerator as f64 / self.denominator as f64
+    /// Subtracts two probabilities, returning an error on overflow
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, ProbabilityError> {
+        self.checked_combine(other, I::checked_sub)
     }
 
-    const fn normalise(&mut self) {
-        let gcd = gcd(self.numerator.unsigned_abs(), self.denominator);
+    /// Multiplies two probabilities, returning an error on overflow
+    pub fn checked_mul(&self, other: &Self) -> Result<Self, ProbabilityError> {
+        // Cancel common factors first to keep the intermediate values small
+        let gcd1 = self.numerator.gcd(&other.denominator);
+        let gcd2 = other.numerator.gcd(&self.denominator);
 
-        if gcd != 1 {
-            self.numerator /= gcd as i64;
-            self.denominator /= gcd;
+        let (gcd1, gcd2) = (nonzero(gcd1), nonzero(gcd2));
+
+        let numerator = (self.numerator.clone() / gcd1.clone())
+            .checked_mul(&(other.numerator.clone() / gcd2.clone()))
+            .ok_or(ProbabilityError::Overflow)?;
+
+        let denominator = (self.denominator.clone() / gcd2)
+            .checked_mul(&(other.denominator.clone() / gcd1))
+            .ok_or(ProbabilityError::Overflow)?;
+
+        Self::from_parts(numerator, denominator)
+    }
+
+    /// Divides two probabilities, returning an error on overflow or division by zero
+    pub fn checked_div(&self, other: &Self) -> Result<Self, ProbabilityError> {
+        self.checked_mul(&other.checked_reciprocal()?)
+    }
+
+    /// Returns the reciprocal, returning an error if the probability is zero
+    pub fn checked_reciprocal(&self) -> Result<Self, ProbabilityError> {
+        Self::from_parts(self.denominator.clone(), self.numerator.clone())
+    }
+
+    /// Adds or subtracts fractions over a common denominator
+    fn checked_combine(&self, other: &Self, op: fn(&I, &I) -> Option<I>) -> Result<Self, ProbabilityError> {
+        let lcm = checked_lcm(&self.denominator, &other.denominator).ok_or(ProbabilityError::Overflow)?;
+
+        let a = self
+            .numerator
+            .checked_mul(&(lcm.clone() / self.denominator.clone()))
+            .ok_or(ProbabilityError::Overflow)?;
+
+        let b = other
+            .numerator
+            .checked_mul(&(lcm.clone() / other.denominator.clone()))
+            .ok_or(ProbabilityError::Overflow)?;
+
+        Self::from_parts(op(&a, &b).ok_or(ProbabilityError::Overflow)?, lcm)
+    }
+
+    /// Converts an integer to the integer type
+    fn integer<T: PrimInt + NumCast>(value: T) -> I {
+        value
+            .to_i64()
+            .and_then(I::from_i64)
+            .unwrap_or_else(|| panic!("{}", ProbabilityError::Overflow))
+    }
+
+    fn normalise(&mut self) {
+        let mut gcd = self.numerator.gcd(&self.denominator);
+
+        if self.denominator.is_negative() {
+            gcd = -gcd;
+        }
+
+        if !gcd.is_one() {
+            self.numerator = self.numerator.clone() / gcd.clone();
+            self.denominator = self.denominator.clone() / gcd;
         }
     }
 }
 
-impl Add for Probability {
+impl<I: ProbabilityInt> Add for Probability<I> {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
@@ -73,42 +273,34 @@ impl Add for Probability {
     }
 }
 
-impl Add<&Probability> for Probability {
+impl<I: ProbabilityInt> Add<&Probability<I>> for Probability<I> {
     type Output = Self;
 
     fn add(self, other: &Self) -> Self {
-        let (lcm, am, bm) = lcm(self.denominator, other.denominator);
-
-        let mut result = Self {
-            numerator: self.numerator * am as i64 + other.numerator * bm as i64,
-            denominator: lcm,
-        };
-
-        result.normalise();
-
-        result
+        self.checked_add(other)
+            .unwrap_or_else(|err| panic!("{err} adding {self} and {other}"))
     }
 }
 
-impl AddAssign for Probability {
+impl<I: ProbabilityInt> AddAssign for Probability<I> {
     fn add_assign(&mut self, other: Self) {
-        *self = *self + other;
+        *self = self.clone() + other;
     }
 }
 
-impl Sum for Probability {
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Self::NEVER, |acc, x| acc + x)
+impl<I: ProbabilityInt> Sum for Probability<I> {
+    fn sum<It: Iterator<Item = Self>>(iter: It) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
     }
 }
 
-impl<'a> Sum<&'a Probability> for Probability {
-    fn sum<I: Iterator<Item = &'a Probability>>(iter: I) -> Self {
-        iter.fold(Probability::NEVER, |acc, x| acc + x)
+impl<'a, I: ProbabilityInt> Sum<&'a Probability<I>> for Probability<I> {
+    fn sum<It: Iterator<Item = &'a Probability<I>>>(iter: It) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
     }
 }
 
-impl Sub for Probability {
+impl<I: ProbabilityInt> Sub for Probability<I> {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
@@ -116,137 +308,127 @@ impl Sub for Probability {
     }
 }
 
-impl Sub<&Probability> for Probability {
+impl<I: ProbabilityInt> Sub<&Probability<I>> for Probability<I> {
     type Output = Self;
 
     fn sub(self, other: &Self) -> Self {
-        let (lcm, am, bm) = lcm(self.denominator, other.denominator);
-
-        let mut result = Self {
-            numerator: self.numerator * am as i64 - other.numerator * bm as i64,
-            denominator: lcm,
-        };
-
-        result.normalise();
-
-        result
+        self.checked_sub(other)
+            .unwrap_or_else(|err| panic!("{err} subtracting {other} from {self}"))
     }
 }
 
-impl SubAssign for Probability {
+impl<I: ProbabilityInt> SubAssign for Probability<I> {
     fn sub_assign(&mut self, other: Self) {
-        *self = *self - other;
+        *self = self.clone() - other;
     }
 }
 
-impl Mul for Probability {
+impl<I: ProbabilityInt> Mul for Probability<I> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
-        let (lcm, am, bm) = lcm(self.denominator, other.denominator);
-
-        let mut ret = Self {
-            numerator: (self.numerator * am as i64) * (other.numerator * bm as i64),
-            denominator: lcm * lcm,
-        };
-
-        ret.normalise();
-
-        ret
+        self.checked_mul(&other)
+            .unwrap_or_else(|err| panic!("{err} multiplying {self} by {other}"))
     }
 }
 
-impl MulAssign for Probability {
+impl<I: ProbabilityInt> MulAssign for Probability<I> {
     fn mul_assign(&mut self, other: Self) {
-        *self = *self * other;
+        *self = self.clone() * other;
     }
 }
 
-impl<I: PrimInt + NumCast> Mul<I> for Probability {
+impl<I: ProbabilityInt, T: PrimInt + NumCast> Mul<T> for Probability<I> {
     type Output = Self;
 
-    fn mul(self, other: I) -> Self {
-        let mut ret = Self {
-            numerator: self.numerator * other.to_i64().unwrap(),
-            denominator: self.denominator,
-        };
-
-        ret.normalise();
-
-        ret
+    fn mul(self, other: T) -> Self {
+        self * Self::from_parts(Self::integer(other), I::one()).unwrap()
     }
 }
 
-impl Div for Probability {
+impl<I: ProbabilityInt> Div for Probability<I> {
     type Output = Self;
 
-    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, other: Self) -> Self {
-        self * other.reciprocal()
+        self.checked_div(&other)
+            .unwrap_or_else(|err| panic!("{err} dividing {self} by {other}"))
     }
 }
 
-impl<I: PrimInt + NumCast> Div<I> for Probability {
+impl<I: ProbabilityInt, T: PrimInt + NumCast> Div<T> for Probability<I> {
     type Output = Self;
 
-    fn div(self, other: I) -> Self {
-        let other = other.to_u64().unwrap();
-
-        let (lcm, am, bm) = lcm(self.denominator, other);
-
-        let mut ret = Self {
-            numerator: (self.numerator * am as i64) * bm as i64,
-            denominator: lcm * lcm,
-        };
-
-        ret.normalise();
-
-        ret
+    fn div(self, other: T) -> Self {
+        self / Self::from_parts(Self::integer(other), I::one()).unwrap()
     }
 }
 
-impl One for Probability {
+impl<I: ProbabilityInt> One for Probability<I> {
     fn one() -> Self {
-        Self::ALWAYS
+        Self {
+            numerator: I::one(),
+            denominator: I::one(),
+        }
     }
 }
 
-impl Zero for Probability {
+impl<I: ProbabilityInt> Zero for Probability<I> {
     fn zero() -> Self {
-        Self::NEVER
+        Self {
+            numerator: I::zero(),
+            denominator: I::one(),
+        }
     }
 
     fn is_zero(&self) -> bool {
-        *self == Self::NEVER
+        self.numerator.is_zero()
     }
 }
 
-impl Display for Probability {
+impl<I: ProbabilityInt> Display for Probability<I> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let string = if self.numerator == 0 {
+        let string = if self.numerator.is_zero() {
             "0".to_string()
         } else {
             format!("{}/{}", self.numerator, self.denominator)
         };
 
-        string.fmt(f)
+        Display::fmt(&string, f)
     }
 }
 
-impl PartialOrd for Probability {
+impl<I: ProbabilityInt> PartialOrd for Probability<I> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for Probability {
+impl<I: ProbabilityInt> Ord for Probability<I> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let (_, am, bm) = lcm(self.denominator, other.denominator);
+        let overflow = || panic!("{} comparing {self} and {other}", ProbabilityError::Overflow);
+
+        let lcm = checked_lcm(&self.denominator, &other.denominator).unwrap_or_else(overflow);
 
-        (self.numerator * am as i64).cmp(&(other.numerator * bm as i64))
+        let scale = |p: &Self| {
+            p.numerator
+                .checked_mul(&(lcm.clone() / p.denominator.clone()))
+                .unwrap_or_else(overflow)
+        };
+
+        scale(self).cmp(&scale(other))
     }
 }
 
+/// Calculates the lowest common multiple of two denominators, returning None on overflow
+fn checked_lcm<I: ProbabilityInt>(a: &I, b: &I) -> Option<I> {
+    (a.clone() / a.gcd(b)).checked_mul(b)
+}
+
+/// Returns one in place of zero so it can be divided by (gcd is zero only if both values are zero)
+fn nonzero<I: ProbabilityInt>(value: I) -> I {
+    if value.is_zero() { I::one() } else { value }
+}
+
 const fn gcd(mut a: u64, mut b: u64) -> u64 {
     while b != 0 {
         let t = b;
@@ -257,11 +439,5 @@ const fn gcd(mut a: u64, mut b: u64) -> u64 {
     a
 }
 
-const fn lcm(a: u64, b: u64) -> (u64, u64, u64) {
-    let lcm = a / gcd(a, b) * b;
-
-    (lcm, lcm / a, lcm / b)
-}
-
 #[cfg(test)]
 mod tests;
@@ -240,3 +240,52 @@ fn test_display2() {
 
     assert_eq!(a.to_string(), "0");
 }
+
+#[test]
+fn test_checked_overflow() {
+    let a = p!(1, 4_000_000_007);
+    let b = p!(1, 4_000_000_009);
+
+    assert_eq!(a.checked_add(&b), Err(ProbabilityError::Overflow));
+    assert_eq!(a.checked_sub(&b), Err(ProbabilityError::Overflow));
+    assert_eq!(a.checked_mul(&b), Err(ProbabilityError::Overflow));
+    assert_eq!(a.checked_div(&Probability::NEVER), Err(ProbabilityError::DivideByZero));
+}
+
+#[test]
+fn test_checked_ok() {
+    let a = p!(1 / 3);
+    let b = p!(1 / 6);
+
+    assert_eq!(a.checked_add(&b), Ok(p!(1 / 2)));
+    assert_eq!(a.checked_sub(&b), Ok(p!(1 / 6)));
+    assert_eq!(a.checked_mul(&b), Ok(p!(1 / 18)));
+    assert_eq!(a.checked_div(&b), Ok(p!(2 / 1)));
+}
+
+#[test]
+#[should_panic(expected = "Probability overflowed")]
+fn test_overflow_panics() {
+    let _ = p!(1, 4_000_000_007) * p!(1, 4_000_000_009);
+}
+
+#[test]
+fn test_from_parts() {
+    assert_eq!(Probability::from_parts(-2i64, -4), Ok(p!(1 / 2)));
+    assert_eq!(Probability::from_parts(2i64, -4), Ok(p!(-1, 2)));
+    assert_eq!(Probability::from_parts(1i64, 0), Err(ProbabilityError::DivideByZero));
+}
+
+#[test]
+fn test_bigint() {
+    let a = BigProbability::from_parts(BigInt::from(1), BigInt::from(4_000_000_007i64)).unwrap();
+    let b = BigProbability::from_parts(BigInt::from(1), BigInt::from(4_000_000_009i64)).unwrap();
+
+    let c = a.clone() * b.clone();
+
+    assert_eq!(c.to_string(), "1/16000000064000000063");
+    assert_eq!(c / a, b);
+
+    // Converted from 64 bit integers
+    assert_eq!(BigProbability::from_probability(p!(3 / 4)).to_string(), "3/4");
+}
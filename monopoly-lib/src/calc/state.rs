@@ -3,81 +3,132 @@ use std::{
     fmt::{Display, Formatter, Result},
 };
 
-use crate::space::{SPACES, Space};
-use crate::strategy::Strategy;
+use strum::IntoEnumIterator;
+
+use crate::boarddef::BoardDefinition;
+use crate::card::Deck;
+use crate::space::Space;
+use crate::strategy::{JailAction, PlayerStrategy};
+
+use super::probability::Probability;
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Hash)]
 pub struct State {
     pub position: usize,
     pub doubles: u8,
     pub jailroll: u8,
+    /// Get out of jail free cards held (bitmask of `Deck::bit`)
+    pub cards: u8,
 }
 
 impl State {
-    pub fn new(doubles: u8, position: usize, jailroll: u8) -> Self {
+    pub fn new(doubles: u8, position: usize, jailroll: u8, cards: u8) -> Self {
         Self {
             position,
             doubles,
             jailroll,
+            cards,
         }
     }
 
-    pub fn create_states(strategy: &Strategy) -> BTreeMap<State, usize> {
+    /// Returns the state at the start of the game: on Go with no doubles rolled and no cards held
+    pub fn start(boarddef: &BoardDefinition) -> Self {
+        Self::new(0, boarddef.find(Space::Go), 0, 0)
+    }
+
+    /// Returns the strategy's jail decisions for each attempt at leaving jail, indexed by whether a get out of jail
+    /// free card is held
+    pub fn jail_actions(strategy: &dyn PlayerStrategy, attempts: u8) -> Vec<[JailAction; 2]> {
+        (1..=attempts)
+            .map(|attempt| [strategy.leave_jail(attempt, false), strategy.leave_jail(attempt, true)])
+            .collect()
+    }
+
+    /// Returns the combinations of get out of jail free cards which can be held on a board
+    pub fn card_masks(boarddef: &BoardDefinition) -> Vec<u8> {
+        let decks = Deck::iter()
+            .filter(|deck| boarddef.deck(*deck).has_jail_card())
+            .fold(0, |mask, deck| mask | deck.bit());
+
+        (0..=decks).filter(|mask| mask & !decks == 0).collect()
+    }
+
+    pub fn create_states(boarddef: &BoardDefinition, jailactions: &[[JailAction; 2]]) -> BTreeMap<State, usize> {
         let mut states = BTreeSet::new();
+        let mut count = 0;
 
-        for doubles in 0..3 {
-            (0..SPACES.len()).for_each(|position| {
-                if SPACES[position] != Space::GoToJail {
-                    states.insert(State {
-                        doubles,
-                        position,
-                        jailroll: 0,
-                    });
-                }
-            });
-        }
+        let jail = boarddef.find(Space::GoToJail);
+
+        for cards in Self::card_masks(boarddef) {
+            for doubles in 0..3 {
+                (0..boarddef.len()).for_each(|position| {
+                    if boarddef[position] != Space::GoToJail {
+                        states.insert(State {
+                            doubles,
+                            position,
+                            jailroll: 0,
+                            cards,
+                        });
+                    }
+                });
+            }
 
-        let jail = Space::find(Space::GoToJail);
+            // Jail states exist for each failed roll to leave jail
+            let has_card = (cards != 0) as usize;
 
-        match strategy {
-            Strategy::PayJail => {
+            let jailstates = 1 + jailactions
+                .iter()
+                .take(jailactions.len() - 1)
+                .take_while(|actions| actions[has_card].roll_probability() != Probability::NEVER)
+                .count();
+
+            for jailroll in 0..jailstates as u8 {
                 states.insert(State {
                     doubles: 0,
                     position: jail,
-                    jailroll: 0,
+                    jailroll,
+                    cards,
                 });
-
-                assert_eq!(states.len(), 118);
-            }
-            Strategy::JailWait => {
-                for jailroll in 0..3 {
-                    states.insert(State {
-                        doubles: 0,
-                        position: jail,
-                        jailroll,
-                    });
-                }
-
-                assert_eq!(states.len(), 120);
             }
+
+            count += 3 * (boarddef.len() - 1) + jailstates;
         }
 
+        assert_eq!(states.len(), count);
+
         states.into_iter().enumerate().map(|(i, s)| (s, i)).collect()
     }
+
+    /// Returns an object which displays the state using a given board layout
+    pub fn display<'a>(&'a self, boarddef: &'a BoardDefinition) -> StateDisplay<'a> {
+        StateDisplay { state: self, boarddef }
+    }
+}
+
+/// Displays a state with the space names from a board layout
+pub struct StateDisplay<'a> {
+    state: &'a State,
+    boarddef: &'a BoardDefinition,
 }
 
-impl Display for State {
+impl Display for StateDisplay<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let mut output = format!("[{}", SPACES[self.position]);
+        let space = self.boarddef[self.state.position];
 
-        if self.doubles > 0 {
-            output.push_str(&format!(" d{}", self.doubles));
+        let mut output = format!("[{}", space);
+
+        if self.state.doubles > 0 {
+            output.push_str(&format!(" d{}", self.state.doubles));
         }
 
-        if SPACES[self.position] == Space::GoToJail {
-            output.push_str(&format!(" r{}", self.jailroll));
+        if space == Space::GoToJail {
+            output.push_str(&format!(" r{}", self.state.jailroll));
         } else {
-            assert!(self.jailroll == 0);
+            assert!(self.state.jailroll == 0);
+        }
+
+        for deck in Deck::iter().filter(|deck| self.state.cards & deck.bit() != 0) {
+            output.push_str(&format!(" +{}", deck.abbrev()));
         }
 
         output.push(']');
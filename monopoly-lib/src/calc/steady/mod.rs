@@ -0,0 +1,334 @@
+use nalgebra::{DMatrix, RowDVector};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+
+use super::probability::{Probability, ProbabilityInt};
+
+/// Primes used for the modular arithmetic (below 2^62 so sums of two residues fit in a u64)
+const PRIMES: [u64; 3] = [4611686018427387847, 4611686018427387817, 4611686018427387787];
+
+/// Number of p-adic digits to calculate between attempts to reconstruct the solution
+const LIFT_STEP: usize = 8;
+
+/// Calculates the exact steady state vector of a transition matrix.
+///
+/// Solves the system `x(P - I) = 0` with the entries of `x` summing to 1 using Dixon's p-adic lifting: the system is
+/// solved modulo a prime, the solution is lifted to a p-adic expansion and rational reconstruction recovers the
+/// fractions. The reconstructed solution is checked with exact integer arithmetic before being returned. Panics if
+/// the matrix doesn't have a unique steady state
+pub fn solve_steady<I: ProbabilityInt>(transmat: &DMatrix<Probability<I>>) -> RowDVector<BigRational> {
+    let system = System::<I::Wide>::new(transmat);
+
+    for prime in PRIMES {
+        if let Some(lu) = ModLu::new(&system.a, prime) {
+            return system.solve(&lu);
+        }
+    }
+
+    panic!("Unable to solve steady state matrix");
+}
+
+/// Integer system of linear equations A·x = b for the steady state, using the wider integer type of the
+/// probabilities
+struct System<W> {
+    /// Coefficient matrix (rows scaled to integers)
+    a: Vec<Vec<W>>,
+    /// Right hand side vector
+    b: Vec<W>,
+}
+
+impl<W: ProbabilityInt> System<W> {
+    /// Sets up the system from the transpose of `P - I` with the last equation replaced by the sum of the entries
+    fn new<I: ProbabilityInt<Wide = W>>(transmat: &DMatrix<Probability<I>>) -> Self {
+        let n = transmat.nrows();
+        assert_eq!(n, transmat.ncols());
+
+        let mut a = Vec::with_capacity(n);
+
+        for i in 0..n - 1 {
+            // Scale the row by the lowest common multiple of the denominators
+            let denominator = (0..n).fold(W::one(), |lcm, j| lcm.lcm(&transmat[(j, i)].denominator().widen()));
+
+            let row = (0..n)
+                .map(|j| {
+                    let mut p = transmat[(j, i)].clone();
+
+                    if i == j {
+                        p -= Probability::one();
+                    }
+
+                    p.numerator()
+                        .widen()
+                        .checked_mul(&(denominator.clone() / p.denominator().widen()))
+                        .expect("Steady state equation overflowed")
+                })
+                .collect();
+
+            a.push(row);
+        }
+
+        a.push(vec![W::one(); n]);
+
+        let mut b = vec![W::zero(); n];
+        b[n - 1] = W::one();
+
+        Self { a, b }
+    }
+
+    /// Solves the system exactly using an LU decomposition modulo a prime
+    fn solve(&self, lu: &ModLu) -> RowDVector<BigRational> {
+        let n = self.b.len();
+        let prime = lu.prime;
+        let modulus_prime = W::from_u64(prime).expect("Prime out of range");
+
+        // p-adic expansion of the solution
+        let mut expansion = vec![BigInt::zero(); n];
+        let mut modulus = BigInt::one();
+
+        // Residual of the system still to be solved
+        let mut residual = self.b.clone();
+
+        loop {
+            for _ in 0..LIFT_STEP {
+                // Solve for the next p-adic digit
+                let digits = lu.solve(&residual.iter().map(|r| residue(r, &modulus_prime)).collect::<Vec<_>>());
+
+                // Update the residual. A·digits ≡ residual (mod p) so the division is exact
+                for (r, row) in residual.iter_mut().zip(&self.a) {
+                    let diff = row
+                        .iter()
+                        .zip(&digits)
+                        .filter(|(a, _)| !a.is_zero())
+                        .try_fold(W::zero(), |acc, (a, d)| {
+                            acc.checked_add(&a.checked_mul(&W::from_u64(*d)?)?)
+                        })
+                        .and_then(|ax| r.checked_sub(&ax))
+                        .expect("Steady state residual overflowed");
+
+                    debug_assert!(diff.is_multiple_of(&modulus_prime));
+
+                    *r = diff / modulus_prime.clone();
+                }
+
+                for (x, d) in expansion.iter_mut().zip(digits) {
+                    *x += &modulus * d;
+                }
+
+                modulus *= prime;
+            }
+
+            if let Some(solution) = self.reconstruct(&expansion, &modulus) {
+                return solution;
+            }
+        }
+    }
+
+    /// Attempts to recover the rational solution from its p-adic expansion, returning it if it solves the system
+    fn reconstruct(&self, expansion: &[BigInt], modulus: &BigInt) -> Option<RowDVector<BigRational>> {
+        let bound: BigInt = (modulus >> 1usize).sqrt();
+
+        // Find the common denominator
+        let mut denominator = BigInt::one();
+
+        for x in expansion {
+            let numerator = symmetric_mod(&(x * &denominator), modulus);
+
+            if numerator.abs() > bound {
+                // Denominator of this entry isn't a factor of the common denominator yet
+                let (_, entrydenom) = rational_reconstruct(&numerator, modulus, &bound)?;
+
+                denominator *= entrydenom;
+            }
+        }
+
+        // Calculate the numerators
+        let numerators = expansion
+            .iter()
+            .map(|x| symmetric_mod(&(x * &denominator), modulus))
+            .collect::<Vec<_>>();
+
+        // Check the solution exactly
+        let solves = self.a.iter().zip(&self.b).all(|(row, b)| {
+            let ax = row
+                .iter()
+                .zip(&numerators)
+                .filter(|(a, _)| !a.is_zero())
+                .map(|(a, x)| x * big(a))
+                .sum::<BigInt>();
+
+            ax == &denominator * big(b)
+        });
+
+        if !solves {
+            return None;
+        }
+
+        Some(RowDVector::from_iterator(
+            numerators.len(),
+            numerators
+                .into_iter()
+                .map(|numerator| BigRational::new(numerator, denominator.clone())),
+        ))
+    }
+}
+
+/// LU decomposition of a matrix modulo a prime
+struct ModLu {
+    prime: u64,
+    /// Combined lower (unit diagonal, below) and upper (on and above diagonal) triangular matrices
+    lu: Vec<Vec<u64>>,
+    /// Row permutation
+    perm: Vec<usize>,
+}
+
+impl ModLu {
+    /// Decomposes a matrix modulo a prime. Returns None if the matrix is singular modulo the prime
+    fn new<W: ProbabilityInt>(a: &[Vec<W>], prime: u64) -> Option<Self> {
+        let n = a.len();
+        let modulus = W::from_u64(prime).expect("Prime out of range");
+
+        let mut lu = a
+            .iter()
+            .map(|row| row.iter().map(|v| residue(v, &modulus)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut perm = (0..n).collect::<Vec<_>>();
+
+        for c in 0..n {
+            // Find a pivot
+            let pivot = (c..n).find(|&r| lu[r][c] != 0)?;
+
+            lu.swap(c, pivot);
+            perm.swap(c, pivot);
+
+            let inv = inverse(lu[c][c], prime);
+            let (upper, lower) = lu.split_at_mut(c + 1);
+            let pivotrow = &upper[c];
+
+            // Eliminate the column from the rows below
+            for row in lower.iter_mut().filter(|row| row[c] != 0) {
+                let factor = mul(row[c], inv, prime);
+
+                row[c] = factor;
+
+                for (v, p) in row[c + 1..].iter_mut().zip(&pivotrow[c + 1..]) {
+                    if *p != 0 {
+                        *v = sub(*v, mul(factor, *p, prime), prime);
+                    }
+                }
+            }
+        }
+
+        Some(Self { prime, lu, perm })
+    }
+
+    /// Solves A·x ≡ b (mod p)
+    fn solve(&self, b: &[u64]) -> Vec<u64> {
+        let n = b.len();
+        let prime = self.prime;
+
+        // Forward substitution
+        let mut y = self.perm.iter().map(|&i| b[i]).collect::<Vec<_>>();
+
+        for i in 0..n {
+            let row = &self.lu[i];
+
+            for j in 0..i {
+                if row[j] != 0 {
+                    y[i] = sub(y[i], mul(row[j], y[j], prime), prime);
+                }
+            }
+        }
+
+        // Back substitution
+        for i in (0..n).rev() {
+            let row = &self.lu[i];
+
+            for j in i + 1..n {
+                if row[j] != 0 {
+                    y[i] = sub(y[i], mul(row[j], y[j], prime), prime);
+                }
+            }
+
+            y[i] = mul(y[i], inverse(row[i], prime), prime);
+        }
+
+        y
+    }
+}
+
+/// Returns the non-negative residue of a value modulo a prime
+fn residue<W: ProbabilityInt>(value: &W, prime: &W) -> u64 {
+    value.mod_floor(prime).to_u64().expect("Residue out of range")
+}
+
+/// Converts an integer to arbitrary precision
+fn big<W: ProbabilityInt>(value: &W) -> BigInt {
+    value.to_bigint().expect("Integer out of range")
+}
+
+/// Multiplies two residues modulo a prime
+fn mul(a: u64, b: u64, prime: u64) -> u64 {
+    ((a as u128 * b as u128) % prime as u128) as u64
+}
+
+/// Subtracts two residues modulo a prime
+fn sub(a: u64, b: u64, prime: u64) -> u64 {
+    if a >= b { a - b } else { a + prime - b }
+}
+
+/// Calculates the inverse of a residue modulo a prime
+fn inverse(a: u64, prime: u64) -> u64 {
+    // Fermat's little theorem
+    let mut result = 1;
+    let mut base = a;
+    let mut exp = prime - 2;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base, prime);
+        }
+
+        base = mul(base, base, prime);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Returns the residue of a value with the smallest magnitude
+fn symmetric_mod(value: &BigInt, modulus: &BigInt) -> BigInt {
+    let value = value.mod_floor(modulus);
+
+    if value > (modulus >> 1) { value - modulus } else { value }
+}
+
+/// Finds the fraction n/d congruent to a value where |n| and d are within a bound
+fn rational_reconstruct(value: &BigInt, modulus: &BigInt, bound: &BigInt) -> Option<(BigInt, BigInt)> {
+    let (mut r0, mut r1) = (modulus.clone(), value.mod_floor(modulus));
+    let (mut t0, mut t1) = (BigInt::zero(), BigInt::one());
+
+    while &r1 > bound {
+        let (q, r2) = r0.div_rem(&r1);
+
+        r0 = std::mem::replace(&mut r1, r2);
+
+        let t2 = &t0 - &q * &t1;
+        t0 = std::mem::replace(&mut t1, t2);
+    }
+
+    if t1.is_zero() || t1.abs() > *bound {
+        return None;
+    }
+
+    if t1.is_negative() {
+        Some((-r1, -t1))
+    } else {
+        Some((r1, t1))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,39 @@
+use super::*;
+use crate::calc::probability::p;
+
+#[test]
+fn test_two_states() {
+    let mat = DMatrix::from_row_slice(2, 2, &[p!(1 / 2), p!(1 / 2), p!(1 / 4), p!(3 / 4)]);
+
+    let steady = solve_steady(&mat);
+
+    assert_eq!(steady[0], BigRational::new(1.into(), 3.into()));
+    assert_eq!(steady[1], BigRational::new(2.into(), 3.into()));
+}
+
+#[test]
+fn test_transient() {
+    // First state is never returned to
+    let mat = DMatrix::from_row_slice(
+        3,
+        3,
+        &[
+            Probability::NEVER,
+            p!(1 / 3),
+            p!(2 / 3),
+            Probability::NEVER,
+            p!(1 / 5),
+            p!(4 / 5),
+            Probability::NEVER,
+            p!(6 / 7),
+            p!(1 / 7),
+        ],
+    );
+
+    let steady = solve_steady(&mat);
+
+    // x1 = x1/5 + 6x2/7, x1 + x2 = 1
+    assert_eq!(steady[0], BigRational::zero());
+    assert_eq!(steady[1], BigRational::new(15.into(), 29.into()));
+    assert_eq!(steady[2], BigRational::new(14.into(), 29.into()));
+}
@@ -0,0 +1,896 @@
+use nalgebra::{Const, DMatrix, Dyn, OMatrix, RowDVector};
+#[cfg(debug_assertions)]
+use nalgebra::{Dim, Matrix, RawStorage};
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+use crate::boarddef::BoardDefinition;
+use crate::card::{Card, CardMove};
+use crate::dice::DiceModel;
+use crate::movereason::MoveReason;
+use crate::rules::Rules;
+use crate::space::Space;
+use crate::strategy::{JailAction, PlayerStrategy};
+
+use super::probability::{MatrixProbability, Probability, ProbabilityInt, p};
+use super::state::{State, StateDisplay};
+use super::steady::solve_steady;
+
+/// Matrix of transition probabilities
+type ProbabilityMatrix<I> = DMatrix<Probability<I>>;
+
+pub struct TransMatrix {
+    /// Board layout used for calculation
+    boarddef: BoardDefinition,
+    /// Dice rolled each turn
+    dice: DiceModel,
+    /// Rule variants in use
+    rules: Rules,
+    /// Strategy decisions for leaving jail for each jail roll count, without and with a card held
+    jailactions: Vec<[JailAction; 2]>,
+    /// Combinations of get out of jail free cards which can be held
+    cardmasks: Vec<u8>,
+    /// List of states used in the Markov chains
+    states: BTreeMap<State, usize>,
+    /// Transition matrix of movement depending on dice roll
+    movemat: DMatrix<MatrixProbability>,
+    /// Transition matrix of jumps after landing on a space with no cards held
+    jumpmat: DMatrix<MatrixProbability>,
+    /// Transition matrix of jumps after landing on a space for each combination of cards held
+    cardjumpmat: DMatrix<MatrixProbability>,
+    /// Combined movement and jump transition matrix
+    combinedmat: DMatrix<MatrixProbability>,
+    /// Combined transition matrix with each transition weighted by the total of the dice rolled
+    dicemat: DMatrix<MatrixProbability>,
+    /// Combined transition matrix steady state vector (exact)
+    steady_exact: RowDVector<BigRational>,
+    /// Combined transition matrix steady state vector
+    steady: OMatrix<f64, Const<1>, Dyn>,
+}
+
+impl TransMatrix {
+    /// Calculates transition matrices and the exact steady state for a given board layout and strategy with the
+    /// classic two six sided dice
+    pub fn new(boarddef: BoardDefinition, strategy: &dyn PlayerStrategy, debug: bool) -> Self {
+        Self::with_dice(boarddef, strategy, DiceModel::classic(), debug)
+    }
+
+    /// Calculates transition matrices and the exact steady state for a given board layout, strategy and dice
+    pub fn with_dice(boarddef: BoardDefinition, strategy: &dyn PlayerStrategy, dice: DiceModel, debug: bool) -> Self {
+        Self::with_rules(boarddef, strategy, dice, Rules::classic(), debug)
+    }
+
+    /// Calculates transition matrices and the exact steady state for a given board layout, strategy, dice and rule
+    /// variants
+    pub fn with_rules(
+        boarddef: BoardDefinition,
+        strategy: &dyn PlayerStrategy,
+        dice: DiceModel,
+        rules: Rules,
+        debug: bool,
+    ) -> Self {
+        rules.validate().expect("Invalid rules");
+
+        // Build jump matrices
+        let cardmasks = State::card_masks(&boarddef);
+        let cardjumpmat = Self::build_jumpmat(&boarddef, &rules, &cardmasks, debug);
+        let jumpmat = Self::collapse_jumpmat(&boarddef, &cardmasks, &cardjumpmat);
+
+        // Get the strategy's jail decisions
+        let jailactions = State::jail_actions(strategy, rules.jail_attempts);
+
+        // Create all possible states
+        let states = State::create_states(&boarddef, &jailactions);
+
+        // Create move matrix
+        let (movemat, combinedmat, dicemat) =
+            Self::build_movemat(&boarddef, &dice, &rules, &states, &cardjumpmat, &jailactions, debug);
+
+        // Calculate steady state vector
+        let (steady_exact, steady) = Self::calc_steady(&combinedmat, debug);
+
+        Self {
+            boarddef,
+            dice,
+            rules,
+            jailactions,
+            cardmasks,
+            states,
+            movemat,
+            jumpmat,
+            cardjumpmat,
+            combinedmat,
+            dicemat,
+            steady_exact,
+            steady,
+        }
+    }
+
+    /// Returns a reference to the board layout
+    pub fn boarddef(&self) -> &BoardDefinition {
+        &self.boarddef
+    }
+
+    /// Returns a reference to the dice model
+    pub fn dice(&self) -> &DiceModel {
+        &self.dice
+    }
+
+    /// Returns a reference to the rule variants
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Returns a reference to the state map
+    pub fn states(&self) -> &BTreeMap<State, usize> {
+        &self.states
+    }
+
+    /// Returns an iterator over the states which displays each with the board layout
+    pub fn states_display(&self) -> impl Iterator<Item = StateDisplay<'_>> + Clone {
+        self.states.keys().map(|state| state.display(&self.boarddef))
+    }
+
+    /// Returns a reference to the movement transition matrix
+    pub fn movemat(&self) -> &DMatrix<MatrixProbability> {
+        &self.movemat
+    }
+
+    /// Returns a reference to the jump transition matrix (with no get out of jail free cards held)
+    pub fn jumpmat(&self) -> &DMatrix<MatrixProbability> {
+        &self.jumpmat
+    }
+
+    /// Returns a reference to the jump transition matrix for each combination of get out of jail free cards held.
+    /// Rows and columns are indexed by card combination then position
+    pub fn cardjumpmat(&self) -> &DMatrix<MatrixProbability> {
+        &self.cardjumpmat
+    }
+
+    /// Returns the combinations of get out of jail free cards which can be held
+    pub fn cardmasks(&self) -> &[u8] {
+        &self.cardmasks
+    }
+
+    /// Returns a reference to the combined movement and jump transition matrix
+    pub fn combinedmat(&self) -> &DMatrix<MatrixProbability> {
+        &self.combinedmat
+    }
+
+    /// Returns a reference to the combined transition matrix with each transition weighted by the total of the dice
+    /// rolled. Multiplying the steady state by this gives the expected dice total for landing in each state
+    pub fn dicemat(&self) -> &DMatrix<MatrixProbability> {
+        &self.dicemat
+    }
+
+    /// Returns a reference to the combined steady state matrix
+    pub fn steady(&self) -> &OMatrix<f64, Const<1>, Dyn> {
+        &self.steady
+    }
+
+    /// Returns a reference to the combined steady state matrix as exact fractions
+    pub fn steady_exact(&self) -> &RowDVector<BigRational> {
+        &self.steady_exact
+    }
+
+    /// Filters and summarises the steady state matrix to a btree
+    pub fn steady_group_sum<T, F>(&self, cb: F) -> BTreeMap<T, f64>
+    where
+        T: Hash + Eq + Ord,
+        F: Fn(&State) -> Option<T>,
+    {
+        self.group_sum(&self.steady, cb)
+    }
+
+    /// Filters and summarises a vector with an entry for each state (such as a steady state vector) to a btree
+    pub fn group_sum<T, F>(&self, vector: &RowDVector<f64>, cb: F) -> BTreeMap<T, f64>
+    where
+        T: Hash + Eq + Ord,
+        F: Fn(&State) -> Option<T>,
+    {
+        let mut summary = BTreeMap::new();
+
+        // Loop all entries in the vector
+        for (prob, state) in vector.iter().zip(self.states.keys()) {
+            // Call callback to get the group this entry belongs to (if any)
+            if let Some(group) = cb(state) {
+                // Add to the group
+                *summary.entry(group).or_insert(0.0) += *prob;
+            }
+        }
+
+        summary
+    }
+
+    /// Filters and summarises the steady state matrix to a group vector and value matrix
+    pub fn steady_group_sum_split<T, F>(&self, cb: F) -> (Vec<T>, DMatrix<f64>)
+    where
+        T: Clone + Hash + Eq + Ord,
+        F: Fn(&State) -> Option<T>,
+    {
+        // Build summary
+        let summary = self.steady_group_sum(cb);
+
+        // Extract group vector
+        let groups = summary.keys().cloned().collect::<Vec<_>>();
+
+        // Extract probability vector
+        let mat = DMatrix::from_iterator(1, summary.len(), summary.values().cloned());
+
+        (groups, mat)
+    }
+
+    /// Filters and summarises the columns of a matrix with a column for each state (such as the transient
+    /// distribution) to a group vector and a matrix with a column for each group
+    pub fn group_columns_sum<T, F>(&self, matrix: &DMatrix<f64>, cb: F) -> (Vec<T>, DMatrix<f64>)
+    where
+        T: Clone + Hash + Eq + Ord,
+        F: Fn(&State) -> Option<T>,
+    {
+        // Find the columns in each group
+        let mut columns: BTreeMap<T, Vec<usize>> = BTreeMap::new();
+
+        for (state, col) in &self.states {
+            if let Some(group) = cb(state) {
+                columns.entry(group).or_default().push(*col);
+            }
+        }
+
+        let (groups, columns): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
+
+        // Sum the columns for each group
+        let mat = DMatrix::from_fn(matrix.nrows(), columns.len(), |row, col| {
+            columns[col].iter().map(|i| matrix[(row, *i)]).sum()
+        });
+
+        (groups, mat)
+    }
+
+    /// Get steady state vector entry by state
+    pub fn steady_ent(&self, state: &State) -> f64 {
+        let ent = self.states.get(state).expect("State not found");
+        self.steady[(0, *ent)]
+    }
+
+    /// Sum entries in the steady state vector
+    pub fn steady_sum<F>(&self, cb: F) -> f64
+    where
+        F: Fn(&State) -> bool,
+    {
+        self.steady
+            .iter()
+            .zip(self.states.keys())
+            .filter_map(|(prob, state)| if cb(state) { Some(*prob) } else { None })
+            .sum()
+    }
+
+    /// Returns the expected number of moves per turn in the steady state (more than one when doubles are rolled)
+    pub fn moves_per_turn(&self) -> f64 {
+        1.0 / self.steady_sum(|state| state.doubles == 0)
+    }
+
+    /// Calculates the probability of being in each state at the end of each of the first `turns` turns, starting
+    /// from a given state. A turn ends after a move which doesn't roll a double (including going to jail). Returns
+    /// one row per turn
+    pub fn transient(&self, start: &State, turns: usize) -> DMatrix<f64> {
+        self.calc_transient(start, turns).0
+    }
+
+    /// Calculates the expected number of times each state is landed on during the first `turns` turns, starting
+    /// from a given state. Extra moves from rolling doubles are counted as well as the state at the end of each turn.
+    /// Returns one row per turn with the cumulative count up to and including that turn
+    pub fn expected_visits(&self, start: &State, turns: usize) -> DMatrix<f64> {
+        self.calc_transient(start, turns).1
+    }
+
+    /// Calculates the transient state distribution and cumulative expected visits for each turn
+    fn calc_transient(&self, start: &State, turns: usize) -> (DMatrix<f64>, DMatrix<f64>) {
+        let dim = self.states.len();
+        let combinedmat = self.combinedmat.map(|p| p.as_f64());
+
+        let mut distribution = DMatrix::zeros(turns, dim);
+        let mut visits = DMatrix::zeros(turns, dim);
+
+        // Probability of being in each state at the start of the turn
+        let mut current = self.start_vector(start);
+        let mut cumulative = RowDVector::zeros(dim);
+
+        for turn in 0..turns {
+            current = self.play_turn(&combinedmat, current, |i, p| {
+                cumulative[i] += p;
+                true
+            });
+
+            distribution.set_row(turn, &current);
+            visits.set_row(turn, &cumulative);
+        }
+
+        (distribution, visits)
+    }
+
+    /// Returns a distribution over the states with certainty of being in one state
+    pub(super) fn start_vector(&self, start: &State) -> RowDVector<f64> {
+        let mut vector = RowDVector::zeros(self.states.len());
+
+        vector[*self.states.get(start).expect("State not found")] = 1.0;
+
+        vector
+    }
+
+    /// Makes the moves for one turn from a distribution over the states, returning the distribution at the end of
+    /// the turn. The callback is called with the probability of landing in each state after each move, and moves
+    /// aren't continued from states it returns false for
+    pub(super) fn play_turn<F>(
+        &self,
+        combinedmat: &DMatrix<f64>,
+        mut current: RowDVector<f64>,
+        mut land: F,
+    ) -> RowDVector<f64>
+    where
+        F: FnMut(usize, f64) -> bool,
+    {
+        let dim = self.states.len();
+        let mut end = RowDVector::zeros(dim);
+
+        // Make moves until no more doubles are rolled (the third double always ends the turn)
+        while current.iter().any(|p| *p != 0.0) {
+            let next = &current * combinedmat;
+
+            current = RowDVector::zeros(dim);
+
+            for ((i, p), state) in next.iter().enumerate().zip(self.states.keys()) {
+                if *p == 0.0 || !land(i, *p) {
+                    continue;
+                }
+
+                if state.doubles > 0 {
+                    // Another move is made in the same turn
+                    current[i] = *p;
+                } else {
+                    end[i] += *p;
+                }
+            }
+        }
+
+        end
+    }
+
+    /// Build the jump transition matrix for each combination of get out of jail free cards held
+    fn build_jumpmat<I: ProbabilityInt>(
+        boarddef: &BoardDefinition,
+        rules: &Rules,
+        cardmasks: &[u8],
+        debug: bool,
+    ) -> ProbabilityMatrix<I> {
+        // Initialise jump transition map
+        let dim = boarddef.len();
+        let mut jumpmat = DMatrix::from_element(dim * cardmasks.len(), dim * cardmasks.len(), Probability::zero());
+
+        // Loop all card combinations and positions and build jump probability map
+        for &cards in cardmasks {
+            for (startidx, startpos) in boarddef.spaces().iter().enumerate() {
+                if debug {
+                    print!("  Jump from {}:", State::new(0, startidx, 0, cards).display(boarddef));
+                }
+
+                // Handle Go to jail / Chance / Community chest jumps
+                let jump_probs = match startpos {
+                    Space::CommunityChest(_) | Space::Chance(_) => {
+                        // Chance or community chest - each card in the deck is equally likely
+                        let decktype = boarddef.deck_type_at(startidx).unwrap();
+                        let mut deck = boarddef.deck(decktype).build_deck();
+
+                        if cards & decktype.bit() != 0 {
+                            // The get out of jail free card is held so is not in the deck
+                            if let Some(elem) = deck.iter().position(|card| *card == Card::GetOutOfJail) {
+                                deck.remove(elem);
+                            }
+                        }
+
+                        let cardprob = p!(1, deck.len() as u64);
+
+                        deck.iter()
+                            .map(|card| match card {
+                                Card::GetOutOfJail => (*startpos, cards | decktype.bit(), cardprob), // Keep the card
+                                _ => match rules.card_movement(*card, boarddef, startidx) {
+                                    CardMove::To(elem) => (boarddef[elem], cards, cardprob), // Move to another space
+                                    CardMove::Jail => (Space::GoToJail, cards, cardprob),    // Go to Jail
+                                    CardMove::Stay => (*startpos, cards, cardprob),          // Stay on the space
+                                },
+                            })
+                            .collect()
+                    }
+                    Space::GoToJail => {
+                        // Go to jail
+                        vec![(Space::GoToJail, cards, Probability::ALWAYS)]
+                    }
+                    _ => vec![(*startpos, cards, Probability::ALWAYS)],
+                };
+
+                #[cfg(debug_assertions)]
+                check_jump_probs(&jump_probs);
+
+                for (pos, newcards, probability) in &jump_probs {
+                    let i = jump_index(cardmasks, dim, startidx, cards);
+                    let j = jump_index(cardmasks, dim, boarddef.find(*pos), *newcards);
+
+                    jumpmat[(i, j)] += Probability::from_probability(*probability);
+
+                    if debug {
+                        if *newcards != cards {
+                            print!(" {pos} (keep card)-{probability}");
+                        } else {
+                            print!(" {pos}-{probability}");
+                        }
+                    }
+                }
+
+                if debug {
+                    println!();
+                }
+            }
+        }
+
+        #[cfg(debug_assertions)]
+        check_matrix(&jumpmat);
+
+        jumpmat
+    }
+
+    /// Build the jump transition matrix with no cards held from the card jump transition matrix
+    fn collapse_jumpmat<I: ProbabilityInt>(
+        boarddef: &BoardDefinition,
+        cardmasks: &[u8],
+        cardjumpmat: &ProbabilityMatrix<I>,
+    ) -> ProbabilityMatrix<I> {
+        let dim = boarddef.len();
+
+        // Sum the jumps to each position over the cards held afterwards
+        DMatrix::from_fn(dim, dim, |i, j| {
+            cardmasks
+                .iter()
+                .map(|&cards| &cardjumpmat[(i, jump_index(cardmasks, dim, j, cards))])
+                .sum()
+        })
+    }
+
+    /// Build the move and combined transition matrices
+    fn build_movemat<I: ProbabilityInt>(
+        boarddef: &BoardDefinition,
+        dice: &DiceModel,
+        rules: &Rules,
+        states: &BTreeMap<State, usize>,
+        jumpmat: &ProbabilityMatrix<I>,
+        jailactions: &[[JailAction; 2]],
+        debug: bool,
+    ) -> (ProbabilityMatrix<I>, ProbabilityMatrix<I>, ProbabilityMatrix<I>) {
+        // Initialise transition maps
+        let mut movemat = DMatrix::from_element(states.len(), states.len(), Probability::zero());
+        let mut combmat = DMatrix::from_element(states.len(), states.len(), Probability::zero());
+        let mut dicemat = DMatrix::from_element(states.len(), states.len(), Probability::zero());
+
+        // Find spaces
+        let visit = boarddef.find(Space::Visit);
+        let g2j = boarddef.find(Space::GoToJail);
+
+        // Combinations of get out of jail free cards which can be held
+        let cardmasks = State::card_masks(boarddef);
+
+        // Possible dice rolls including the speed die. Paying or using a card to leave jail rolls as normal so needs
+        // the speed die. Rolling to leave jail only uses the main dice, but each main dice roll is split over the speed
+        // die faces in proportion to their probabilities, so the speed die cancels out when they are summed
+        let rolls = dice.rolls(false);
+
+        // Loop all start states
+        for (start, &i) in states.iter() {
+            if debug {
+                println!("From {}:", start.display(boarddef));
+            }
+
+            // For each possible dice roll
+            for (roll, roll_prob) in &rolls {
+                let double = roll.double();
+
+                if debug {
+                    print!(
+                        "  {} + {roll} ({}, {}) =>",
+                        start.display(boarddef),
+                        roll.total(),
+                        if double { "double" } else { "not double" }
+                    );
+                }
+
+                // Calculate states after rolling the dice with their probabilities
+                let move_states = if boarddef[start.position] == Space::GoToJail {
+                    // In jail
+                    let action = jailactions[start.jailroll as usize][(start.cards != 0) as usize];
+                    let rollprob = action.roll_probability();
+                    let mut move_states = Vec::new();
+
+                    if rollprob != Probability::NEVER {
+                        // Wait in jail (the speed die is not rolled)
+                        let move_state = if double {
+                            // Rolled a double, move from just visting, do not get another go
+                            State::new(0, (visit + roll.dice_total() as usize) % boarddef.len(), 0, start.cards)
+                        } else {
+                            // Did not roll a double
+                            let jailrolls = start.jailroll + 1;
+
+                            if jailrolls as usize == jailactions.len() {
+                                // Used all attempts - move to just visiting
+                                State::new(0, visit, 0, start.cards)
+                            } else {
+                                State::new(0, g2j, jailrolls, start.cards)
+                            }
+                        };
+
+                        move_states.push((move_state, rollprob, roll.dice_total()));
+                    }
+
+                    if rollprob != Probability::ALWAYS {
+                        // Pay or use a card to get out of jail
+                        let cards = if start.cards != 0 && action != JailAction::Pay {
+                            // Use a get out of jail free card (chance first)
+                            start.cards & (start.cards - 1)
+                        } else {
+                            start.cards
+                        };
+
+                        let position = dice.destination(boarddef, visit, roll);
+
+                        // Doubles are not counted when going straight back to jail
+                        let doubles = if double && boarddef[position] != Space::GoToJail {
+                            1
+                        } else {
+                            0
+                        };
+
+                        let move_state = State::new(doubles, position, 0, cards);
+
+                        move_states.push((move_state, Probability::ALWAYS - rollprob, roll.total()));
+                    }
+
+                    move_states
+                } else {
+                    // Normal move
+                    let mut doubles = if !double { 0 } else { start.doubles + 1 };
+
+                    let move_state = if doubles == 3 && rules.triple_double_jail {
+                        // 3 doubles in a row, go to jail
+                        State::new(0, g2j, 0, start.cards)
+                    } else {
+                        if doubles == 3 {
+                            // 3 doubles in a row ends the turn
+                            doubles = 0;
+                        }
+
+                        let position = dice.destination(boarddef, start.position, roll);
+
+                        if boarddef[position] == Space::GoToJail {
+                            // Go to jail
+                            doubles = 0;
+                        }
+
+                        State::new(doubles, position, 0, start.cards)
+                    };
+
+                    vec![(move_state, Probability::ALWAYS, roll.total())]
+                };
+
+                for (move_state, prob, total) in move_states {
+                    let prob = Probability::from_probability(*roll_prob * prob);
+
+                    // Set move matrix entry
+                    let j = *states.get(&move_state).unwrap();
+                    movemat[(i, j)] += prob.clone();
+
+                    if debug {
+                        print!(" {}×{}", move_state.display(boarddef), prob);
+                    }
+
+                    // Process jumps
+                    let mut jump_state = JumpState {
+                        boarddef,
+                        i,
+                        states,
+                        cardmasks: &cardmasks,
+                        jumpmat,
+                        combmat: &mut combmat,
+                        dicemat: &mut dicemat,
+                        dice: total,
+                        roll_prob: Probability::from_probability(*roll_prob),
+                        debug,
+                        first: true,
+                    };
+
+                    Self::process_jumps(&mut jump_state, move_state, prob);
+
+                    if debug {
+                        print!(" )");
+                    }
+                }
+
+                if debug {
+                    println!();
+                }
+            }
+
+            #[cfg(debug_assertions)]
+            check_matrix(&combmat.row(i));
+        }
+
+        #[cfg(debug_assertions)]
+        check_matrix(&movemat);
+
+        #[cfg(debug_assertions)]
+        check_matrix(&combmat);
+
+        (movemat, combmat, dicemat)
+    }
+
+    /// Calculate probability of a jump recursively
+    fn process_jumps<I: ProbabilityInt>(jump_state: &mut JumpState<I>, move_state: State, parent_prob: Probability<I>) {
+        // Get jumps from the new position
+        let dim = jump_state.boarddef.len();
+        let jumps = jump_state.jumpmat.row(jump_index(
+            jump_state.cardmasks,
+            dim,
+            move_state.position,
+            move_state.cards,
+        ));
+
+        // Loop all possible jumps
+        for (j, prob) in jumps.iter().enumerate() {
+            if prob.is_zero() {
+                continue;
+            }
+
+            let (pos, cards) = (j % dim, jump_state.cardmasks[j / dim]);
+
+            let prob = prob.clone() * parent_prob.clone();
+
+            let doubles = if jump_state.boarddef[pos] == Space::GoToJail {
+                0
+            } else {
+                move_state.doubles
+            };
+
+            let new_state = State::new(doubles, pos, move_state.jailroll, cards);
+
+            if pos == move_state.position {
+                if jump_state.debug {
+                    if jump_state.first {
+                        print!(
+                            " × ( {}×{}",
+                            new_state.display(jump_state.boarddef),
+                            prob.clone() / jump_state.roll_prob.clone()
+                        );
+                        jump_state.first = false;
+                    } else {
+                        print!(
+                            " + {}×{}",
+                            new_state.display(jump_state.boarddef),
+                            prob.clone() / jump_state.roll_prob.clone()
+                        );
+                    }
+                }
+
+                // Get matrix column number
+                let j = *jump_state.states.get(&new_state).unwrap();
+
+                // Set combined matrix entries
+                jump_state.combmat[(jump_state.i, j)] += prob.clone();
+                jump_state.dicemat[(jump_state.i, j)] += prob * jump_state.dice;
+            } else {
+                // Recurse
+                Self::process_jumps(jump_state, new_state, prob);
+            }
+        }
+    }
+
+    /// Calculate the exact steady state vector from the combined transition matrix, and convert it to floating point
+    fn calc_steady(
+        combinedmat: &DMatrix<MatrixProbability>,
+        debug: bool,
+    ) -> (RowDVector<BigRational>, OMatrix<f64, Const<1>, Dyn>) {
+        // Solve the system of linear equations exactly
+        let steady_exact = solve_steady(combinedmat);
+
+        // Convert to floating point
+        let steady = steady_exact.map(|p| p.to_f64().expect("Steady state probability out of range"));
+
+        if debug {
+            println!("Steady state vector ({}):", steady.ncols());
+            println!("{}", steady.transpose());
+        }
+
+        (steady_exact, steady)
+    }
+
+    /// Calculate the move reason probability matrix (move reasons × spaces)
+    pub fn calc_movereason_probabilty(&self) -> DMatrix<f64> {
+        // Initialsie the matrix
+        let mut probabilities = DMatrix::zeros(MoveReason::uint_count(), self.boarddef.len());
+
+        // Calculate chance, community chest and chance->community chest matrix rows
+        let (chprob, ccprob, chccprob) = self.calc_chance_cc_prob();
+
+        probabilities.set_row(MoveReason::CCCard as usize, &ccprob);
+        probabilities.set_row(MoveReason::CHCard as usize, &chprob);
+        probabilities.set_row(MoveReason::CHCardCCCard as usize, &chccprob);
+
+        // Find spaces
+        let g2j = self.boarddef.find(Space::GoToJail);
+        let visit = self.boarddef.find(Space::Visit);
+
+        // Probability of rolling a double
+        let doubleprob = self.dice.double_probability();
+
+        // Probability of not rolling double while in jail, and of leaving jail after the last attempt
+        let mut nodoubleprob = 0.0;
+        let mut exitjailprob = 0.0;
+
+        for state in self.states.keys().filter(|state| state.position == g2j) {
+            let rollprob = self.jailactions[state.jailroll as usize][(state.cards != 0) as usize].roll_probability();
+
+            if rollprob != Probability::NEVER {
+                let prob = ((Probability::ALWAYS - doubleprob) * rollprob).as_f64() * self.steady_ent(state);
+
+                if state.jailroll as usize + 1 == self.jailactions.len() {
+                    exitjailprob += prob;
+                } else {
+                    nodoubleprob += prob;
+                }
+            }
+        }
+
+        probabilities[(MoveReason::NoDouble as usize, g2j)] = nodoubleprob;
+        probabilities[(MoveReason::ExitJail as usize, visit)] = exitjailprob;
+
+        // Probability of rolling double 3 times while not in jail
+        let tripledouble = if self.rules.triple_double_jail {
+            doubleprob.as_f64() * self.steady_sum(|state| state.doubles == 2)
+        } else {
+            0.0
+        };
+
+        probabilities[(MoveReason::TripleDouble as usize, g2j)] = tripledouble;
+
+        // Probability of landing on go to jail space
+        probabilities[(MoveReason::GoToJail as usize, g2j)] =
+            self.steady_sum(|state| state.position == g2j) - probabilities.column(g2j).sum();
+
+        probabilities
+    }
+
+    // Calculate chance, community chest and chance->community chest move reason probability matrix rows
+    fn calc_chance_cc_prob(&self) -> (RowDVector<f64>, RowDVector<f64>, RowDVector<f64>) {
+        let dim = self.boarddef.len();
+
+        // Calculate probability of first landing on a space with each combination of cards held. Landing with fewer
+        // cards and drawing a get out of jail free card also ends on the space so is subtracted first
+        let mut land1: BTreeMap<(usize, u8), f64> = BTreeMap::new();
+
+        for ((pos, cards), p) in self.steady_group_sum(|state| Some((state.position, state.cards))) {
+            let i = self.jump_index(pos, cards);
+
+            let kept = land1
+                .range((pos, 0)..(pos, cards))
+                .map(|(&(_, from), p0)| p0 * self.cardjumpmat[(self.jump_index(pos, from), i)].as_f64())
+                .sum::<f64>();
+
+            land1.insert((pos, cards), (p - kept) / self.cardjumpmat[(i, i)].as_f64());
+        }
+
+        // Initialise probability vectors
+        let mut chprob = RowDVector::zeros(dim);
+        let mut ccprob = RowDVector::zeros(dim);
+        let mut chccprob = RowDVector::zeros(dim);
+
+        for (&(pos, cards), p1) in &land1 {
+            let row = self.cardjumpmat.row(self.jump_index(pos, cards));
+
+            match self.boarddef[pos] {
+                Space::CommunityChest(_) => {
+                    // Find jump probabilities for community chest space (excluding self)
+                    for (j, p2) in row.iter().enumerate().filter(|(j, _)| j % dim != pos) {
+                        // Calculate the resultant probability (landing on space then jumping)
+                        ccprob[j % dim] += p1 * p2.as_f64();
+                    }
+                }
+                Space::Chance(_) => {
+                    // Find jump probabilities for chance space (excluding self)
+                    for (j, p2) in row.iter().enumerate().filter(|(j, _)| j % dim != pos) {
+                        // Calculate the resultant probability (landing on space then jumping)
+                        chprob[j % dim] += p1 * p2.as_f64();
+
+                        if matches!(self.boarddef[j % dim], Space::CommunityChest(_)) {
+                            // Probability of landing on chance then going to community chest
+                            let chccprobmult = p1 * p2.as_f64();
+
+                            // Find jump probabilities for the community chest space (excluding self)
+                            for (k, p3) in self
+                                .cardjumpmat
+                                .row(j)
+                                .iter()
+                                .enumerate()
+                                .filter(|(k, _)| k % dim != j % dim)
+                            {
+                                let (j, k) = (j % dim, k % dim);
+                                let p = chccprobmult * p3.as_f64();
+
+                                // Move the probability from the community chest row to the
+                                // chance -> community chest row
+                                chccprob[k] += p;
+                                ccprob[k] -= p;
+
+                                // Subtract the probability from the chance -> community chest probability
+                                chprob[j] -= p;
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        (chprob, ccprob, chccprob)
+    }
+
+    /// Returns the card jump matrix index for a position with a combination of cards held
+    fn jump_index(&self, position: usize, cards: u8) -> usize {
+        jump_index(&self.cardmasks, self.boarddef.len(), position, cards)
+    }
+}
+
+/// Returns the card jump matrix index for a position with a combination of cards held
+fn jump_index(cardmasks: &[u8], dim: usize, position: usize, cards: u8) -> usize {
+    let maskidx = cardmasks
+        .iter()
+        .position(|&mask| mask == cards)
+        .expect("Card combination not found");
+
+    maskidx * dim + position
+}
+
+struct JumpState<'a, I> {
+    boarddef: &'a BoardDefinition,         // Board layout
+    i: usize,                              // Matrix row (from)
+    states: &'a BTreeMap<State, usize>,    // State map
+    cardmasks: &'a [u8],                   // Card combinations
+    jumpmat: &'a ProbabilityMatrix<I>,     // Card jump matrix
+    combmat: &'a mut ProbabilityMatrix<I>, // Combined matrix
+    dicemat: &'a mut ProbabilityMatrix<I>, // Combined matrix weighted by dice total
+    dice: u8,                              // Dice total rolled
+    roll_prob: Probability<I>,             // Probability of the dice roll
+    debug: bool,
+    first: bool,
+}
+
+#[cfg(debug_assertions)]
+/// Checks the sum of probabilities is 1.0
+fn check_jump_probs(jump_probs: &[(Space, u8, Probability)]) {
+    assert_eq!(
+        jump_probs.iter().map(|(_, _, p)| p).copied().sum::<Probability>(),
+        Probability::ALWAYS
+    );
+}
+
+#[cfg(debug_assertions)]
+/// Checks the sum of probabilities in each matrix row is 1.0
+fn check_matrix<I, R, C, S>(transprob: &Matrix<Probability<I>, R, C, S>)
+where
+    I: ProbabilityInt,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<Probability<I>, R, C>,
+{
+    for row in transprob.row_iter() {
+        assert_eq!(row.iter().sum::<Probability<I>>(), Probability::one());
+    }
+}
+
+#[cfg(test)]
+mod tests;
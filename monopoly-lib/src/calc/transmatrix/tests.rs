@@ -0,0 +1,313 @@
+use super::*;
+use crate::card::{DeckDefinition, DeckEntry};
+use crate::dice::Die;
+use crate::strategy::{JailRolls, JailWait, MixedJail, PayJail};
+
+#[test]
+fn test_strategy_states() {
+    let boarddef = BoardDefinition::default();
+    let g2j = boarddef.find(Space::GoToJail);
+    let visit = boarddef.find(Space::Visit);
+
+    for (strategy, jailstates) in [
+        (&PayJail as &dyn PlayerStrategy, 1),
+        (&JailRolls(1), 2),
+        (&JailRolls(2), 3),
+        (&JailWait, 3),
+        (&MixedJail([p!(1 / 2), p!(1 / 3), p!(1 / 4)]), 3),
+        (&MixedJail([p!(1 / 2), Probability::NEVER, p!(1 / 4)]), 2),
+    ] {
+        let mat = TransMatrix::new(boarddef.clone(), strategy, false);
+
+        assert_eq!(
+            mat.states().keys().filter(|s| s.position == g2j).count(),
+            jailstates * mat.cardmasks().len()
+        );
+        assert!((mat.steady_sum(|_| true) - 1.0).abs() < 1e-9);
+        assert_eq!(
+            mat.steady_exact().iter().sum::<BigRational>(),
+            BigRational::from_integer(1.into())
+        );
+
+        // Only leave jail after a failed third roll if rolling on the third attempt
+        let reasons = mat.calc_movereason_probabilty();
+
+        assert_eq!(
+            reasons[(MoveReason::ExitJail as usize, visit)] > 0.0,
+            jailstates == 3 && strategy.leave_jail(3, false).roll_probability() != Probability::NEVER
+        );
+        assert_eq!(reasons[(MoveReason::NoDouble as usize, g2j)] > 0.0, jailstates > 1);
+    }
+}
+
+#[test]
+fn test_equivalent_strategies() {
+    let boarddef = BoardDefinition::default();
+
+    let steady = |strategy: &dyn PlayerStrategy| TransMatrix::new(boarddef.clone(), strategy, false).combinedmat;
+
+    // Rolling every time is the same as waiting, never rolling is the same as paying
+    assert_eq!(steady(&JailRolls(3)), steady(&JailWait));
+    assert_eq!(steady(&MixedJail([Probability::ALWAYS; 3])), steady(&JailWait));
+    assert_eq!(steady(&JailRolls(0)), steady(&PayJail));
+    assert_eq!(steady(&MixedJail([Probability::NEVER; 3])), steady(&PayJail));
+}
+
+#[test]
+fn test_jail_cards() {
+    /// Uses a get out of jail free card straight away if held, otherwise rolls
+    #[derive(Debug)]
+    struct CardOrWait;
+
+    impl PlayerStrategy for CardOrWait {
+        fn name(&self) -> &str {
+            "Use a card or roll to leave jail"
+        }
+
+        fn leave_jail(&self, _attempt: u8, has_card: bool) -> JailAction {
+            if has_card {
+                JailAction::UseCard
+            } else {
+                JailAction::Roll
+            }
+        }
+    }
+
+    let boarddef = BoardDefinition::default();
+    let g2j = boarddef.find(Space::GoToJail);
+
+    // Same board with the get out of jail free cards replaced by inconsequential cards
+    let nocards = |deck: &DeckDefinition| {
+        DeckDefinition::new(
+            deck.cards()
+                .iter()
+                .filter(|entry| entry.card != Card::GetOutOfJail)
+                .cloned()
+                .collect(),
+            Some(deck.len()),
+        )
+    };
+
+    let cardless = BoardDefinition::new(
+        "No cards",
+        boarddef.spaces().to_vec(),
+        nocards(boarddef.chance()),
+        nocards(boarddef.community_chest()),
+    )
+    .unwrap();
+
+    let cards = TransMatrix::new(boarddef, &CardOrWait, false);
+    let nocards = TransMatrix::new(cardless, &CardOrWait, false);
+
+    assert_eq!(cards.cardmasks(), [0, 1, 2, 3]);
+    assert_eq!(nocards.cardmasks(), [0]);
+    // Three jail states with no card held, one when holding cards as they are used straight away
+    assert_eq!(cards.states().len(), 4 * (nocards.states().len() - 3) + 3 + 3);
+    assert!((cards.steady_sum(|_| true) - 1.0).abs() < 1e-9);
+
+    // Keeping a card doesn't move the player
+    assert_eq!(cards.jumpmat(), nocards.jumpmat());
+
+    // Drawing the last card from a deck while holding the other deck's card
+    let ch1 = cards.boarddef().find(Space::Chance(0));
+
+    assert_eq!(
+        cards.cardjumpmat()[(cards.jump_index(ch1, 2), cards.jump_index(ch1, 3))],
+        MatrixProbability::from_probability(p!(1 / 16))
+    );
+    assert_eq!(
+        cards.cardjumpmat()[(cards.jump_index(ch1, 1), cards.jump_index(ch1, 1))],
+        MatrixProbability::from_probability(p!(5 / 15))
+    );
+
+    // Using cards shortens the time spent in jail
+    let jail = |mat: &TransMatrix| mat.steady_sum(|state| state.position == g2j && state.jailroll > 0);
+
+    assert!(jail(&cards) < jail(&nocards));
+}
+
+#[test]
+fn test_short_board() {
+    // Short board where a double from just visiting lands on go to jail
+    let boarddef = BoardDefinition::new(
+        "Short",
+        vec![
+            Space::Go,
+            Space::Property(0, 0),
+            Space::Chance(0),
+            Space::Rail(0),
+            Space::Visit,
+            Space::Property(1, 0),
+            Space::Chance(1),
+            Space::Rail(1),
+            Space::GoToJail,
+        ],
+        DeckDefinition::new(
+            vec![
+                DeckEntry {
+                    card: Card::GetOutOfJail,
+                    count: 1,
+                },
+                DeckEntry {
+                    card: Card::Back(2),
+                    count: 1,
+                },
+            ],
+            Some(4),
+        ),
+        DeckDefinition::classic_community_chest(),
+    )
+    .unwrap();
+
+    for strategy in [&PayJail as &dyn PlayerStrategy, &JailRolls(1)] {
+        let mat = TransMatrix::new(boarddef.clone(), strategy, false);
+
+        assert!(mat.cardmasks().len() > 1);
+        assert!(
+            mat.states()
+                .keys()
+                .all(|state| boarddef[state.position] != Space::GoToJail || state.doubles == 0)
+        );
+        assert!((mat.steady_sum(|_| true) - 1.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_transient() {
+    let boarddef = BoardDefinition::default();
+    let mat = TransMatrix::new(boarddef.clone(), &PayJail, false);
+
+    let start = State::start(&boarddef);
+    let transient = mat.transient(&start, 3);
+    let visits = mat.expected_visits(&start, 3);
+
+    assert_eq!(transient.shape(), (3, mat.states().len()));
+
+    for turn in 0..3 {
+        // Every turn ends somewhere without a double pending
+        let (doubles, bydoubles) = mat.group_columns_sum(&transient, |state| Some(state.doubles));
+
+        assert_eq!(doubles, vec![0, 1, 2]);
+        assert!((bydoubles[(turn, 0)] - 1.0).abs() < 1e-12);
+        assert_eq!(bydoubles[(turn, 1)] + bydoubles[(turn, 2)], 0.0);
+    }
+
+    // One move per turn plus another for each double rolled, up to three. Going to jail ends the turn early
+    let moves = 1.0 + 1.0 / 6.0 + 1.0 / 36.0;
+
+    for turn in 0..3 {
+        let prev = if turn == 0 { 0.0 } else { visits.row(turn - 1).sum() };
+        let turnmoves = visits.row(turn).sum() - prev;
+
+        assert!(turnmoves > 1.0 && turnmoves <= moves + 1e-12);
+    }
+
+    // The second space can't be reached on the first turn
+    let (spaces, byspace) = mat.group_columns_sum(&transient, |state| Some(state.position));
+
+    assert_eq!(spaces.len(), boarddef.len());
+    assert_eq!(byspace[(0, 1)], 0.0);
+    assert!(byspace[(0, 7)] > 0.0);
+    assert!(byspace[(1, 1)] > 0.0);
+}
+
+#[test]
+fn test_dice_models() {
+    let boarddef = BoardDefinition::default();
+    let classic = TransMatrix::new(boarddef.clone(), &PayJail, false);
+
+    assert_eq!(
+        TransMatrix::with_dice(boarddef.clone(), &PayJail, DiceModel::classic(), false).steady_exact(),
+        classic.steady_exact()
+    );
+
+    // Loaded dice roll more doubles so take more moves per turn and go to jail for triple doubles more often
+    let die = Die::loaded(vec![1, 1, 1, 1, 1, 3]);
+    let loaded = DiceModel::new(vec![die.clone(), die], None).unwrap();
+    let loaded = TransMatrix::with_dice(boarddef.clone(), &PayJail, loaded, false);
+
+    let g2j = boarddef.find(Space::GoToJail);
+    let tripledouble = |mat: &TransMatrix| mat.calc_movereason_probabilty()[(MoveReason::TripleDouble as usize, g2j)];
+
+    assert!((loaded.steady_sum(|_| true) - 1.0).abs() < 1e-9);
+    assert!(loaded.moves_per_turn() > classic.moves_per_turn());
+    assert!(tripledouble(&loaded) > tripledouble(&classic));
+
+    // Mr. Monopoly on the speed die moves players on to spaces which can be bought
+    let mega = TransMatrix::with_dice(boarddef.clone(), &PayJail, DiceModel::mega(), false);
+    let buyable = |mat: &TransMatrix| mat.steady_sum(|state| boarddef[state.position].price().is_some());
+
+    assert!((mega.steady_sum(|_| true) - 1.0).abs() < 1e-9);
+    assert!(buyable(&mega) > buyable(&classic));
+}
+
+#[test]
+fn test_rules() {
+    let boarddef = BoardDefinition::default();
+    let g2j = boarddef.find(Space::GoToJail);
+    let visit = boarddef.find(Space::Visit);
+
+    let classic = TransMatrix::new(boarddef.clone(), &JailWait, false);
+
+    let rules = Rules {
+        triple_double_jail: false,
+        jail_attempts: 2,
+        ..Rules::classic()
+    };
+    let house = TransMatrix::with_rules(boarddef.clone(), &JailWait, DiceModel::classic(), rules, false);
+
+    assert!((house.steady_sum(|_| true) - 1.0).abs() < 1e-9);
+
+    // One fewer jail state for each combination of cards held
+    assert_eq!(house.states().len() + house.cardmasks().len(), classic.states().len());
+
+    // Never go to jail for rolling doubles, and less time is spent in jail
+    let reasons = house.calc_movereason_probabilty();
+    let jail = |mat: &TransMatrix| mat.steady_sum(|state| state.position == g2j);
+
+    assert_eq!(reasons[(MoveReason::TripleDouble as usize, g2j)], 0.0);
+    assert!(reasons[(MoveReason::ExitJail as usize, visit)] > 0.0);
+    assert!(jail(&house) < jail(&classic));
+    assert!(house.moves_per_turn() > classic.moves_per_turn());
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_big_probabilities() {
+    // Heavily loaded dice and a large chance deck give probabilities too large for 64 bit integers
+    let boarddef = BoardDefinition::new(
+        "Short",
+        vec![
+            Space::Go,
+            Space::Property(0, 0),
+            Space::Chance(0),
+            Space::Rail(0),
+            Space::Visit,
+            Space::Property(1, 0),
+            Space::Chance(1),
+            Space::Rail(1),
+            Space::GoToJail,
+        ],
+        DeckDefinition::new(
+            vec![
+                DeckEntry {
+                    card: Card::GetOutOfJail,
+                    count: 1,
+                },
+                DeckEntry {
+                    card: Card::Back(2),
+                    count: 1,
+                },
+            ],
+            Some(10007),
+        ),
+        DeckDefinition::classic_community_chest(),
+    )
+    .unwrap();
+
+    let dice = DiceModel::new(vec![Die::loaded(vec![1, 1000002]), Die::loaded(vec![1, 1000032])], None).unwrap();
+
+    let mat = TransMatrix::with_dice(boarddef, &PayJail, dice, false);
+
+    assert!((mat.steady_sum(|_| true) - 1.0).abs() < 1e-9);
+}
@@ -0,0 +1,208 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+use crate::boarddef::BoardDefinition;
+use crate::space::{PropertySet, Space};
+
+/// Chance or community chest card
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Card {
+    /// Advance to a given space
+    GoTo(Space),
+    /// Go directly to jail
+    GoJail,
+    /// Advance to the nearest space in a set (eg. nearest station or utility)
+    GoNext(PropertySet),
+    /// Move back a number of spaces
+    Back(u8),
+    /// Get out of jail free. Kept by the player until used
+    GetOutOfJail,
+    /// Card which does not move the player
+    Inconsequential,
+}
+
+/// Movement resulting from a card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardMove {
+    /// Stay on the current space
+    Stay,
+    /// Go to jail
+    Jail,
+    /// Move to a board position
+    To(usize),
+}
+
+impl Card {
+    /// Calculates the movement caused by drawing this card at a given position
+    pub fn movement(&self, boarddef: &BoardDefinition, position: usize) -> CardMove {
+        match self {
+            Card::GoTo(space) => CardMove::To(boarddef.find(*space)),
+            Card::GoJail => CardMove::Jail,
+            Card::GoNext(set) => CardMove::To(boarddef.next_in_set(position, *set)),
+            Card::Back(n) => CardMove::To(boarddef.back(position, *n as usize)),
+            Card::GetOutOfJail | Card::Inconsequential => CardMove::Stay,
+        }
+    }
+}
+
+/// Chance or community chest card deck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum Deck {
+    Chance,
+    CommunityChest,
+}
+
+impl Deck {
+    /// Returns the bit used to represent a held get out of jail free card from this deck
+    pub fn bit(&self) -> u8 {
+        match self {
+            Deck::Chance => 1,
+            Deck::CommunityChest => 2,
+        }
+    }
+
+    /// Returns a short name for the deck
+    pub fn abbrev(&self) -> &str {
+        match self {
+            Deck::Chance => "CH",
+            Deck::CommunityChest => "CC",
+        }
+    }
+}
+
+/// Card deck entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeckEntry {
+    /// The card
+    pub card: Card,
+    /// Number of copies of the card in the deck
+    #[serde(default = "DeckEntry::default_count")]
+    pub count: usize,
+}
+
+impl DeckEntry {
+    fn default_count() -> usize {
+        1
+    }
+}
+
+/// Card deck definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeckDefinition {
+    /// Cards in the deck
+    cards: Vec<DeckEntry>,
+    /// Total number of cards in the deck. The deck is padded with inconsequential cards to this size
+    size: Option<usize>,
+}
+
+impl DeckDefinition {
+    /// Create a new deck definition
+    pub fn new(cards: Vec<DeckEntry>, size: Option<usize>) -> Self {
+        Self { cards, size }
+    }
+
+    /// The classic chance deck
+    pub fn classic_chance() -> Self {
+        Self::from_cards(
+            &[
+                (Card::GoTo(Space::Go), 1),
+                (Card::GoJail, 1),
+                (Card::GoTo(Space::Property(2, 0)), 1),
+                (Card::GoTo(Space::Property(4, 2)), 1),
+                (Card::GoTo(Space::Property(7, 1)), 1),
+                (Card::GoTo(Space::Rail(0)), 1),
+                (Card::GoNext(PropertySet::Station), 2),
+                (Card::GoNext(PropertySet::Utility), 1),
+                (Card::Back(3), 1),
+                (Card::GetOutOfJail, 1),
+            ],
+            16,
+        )
+    }
+
+    /// The classic community chest deck
+    pub fn classic_community_chest() -> Self {
+        Self::from_cards(
+            &[(Card::GoJail, 1), (Card::GoTo(Space::Go), 1), (Card::GetOutOfJail, 1)],
+            16,
+        )
+    }
+
+    fn from_cards(cards: &[(Card, usize)], size: usize) -> Self {
+        Self {
+            cards: cards.iter().map(|&(card, count)| DeckEntry { card, count }).collect(),
+            size: Some(size),
+        }
+    }
+
+    /// Returns the card entries in the deck
+    pub fn cards(&self) -> &[DeckEntry] {
+        &self.cards
+    }
+
+    /// Returns the number of cards in the deck
+    pub fn len(&self) -> usize {
+        self.size.unwrap_or_else(|| self.card_count())
+    }
+
+    /// Returns true if the deck contains no cards
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the deck contains a get out of jail free card
+    pub fn has_jail_card(&self) -> bool {
+        self.cards
+            .iter()
+            .any(|entry| entry.card == Card::GetOutOfJail && entry.count > 0)
+    }
+
+    /// Builds the deck of cards (unshuffled)
+    pub fn build_deck(&self) -> VecDeque<Card> {
+        let mut deck = VecDeque::new();
+
+        for entry in &self.cards {
+            deck.extend(std::iter::repeat_n(entry.card, entry.count));
+        }
+
+        while deck.len() < self.len() {
+            deck.push_back(Card::Inconsequential);
+        }
+
+        deck
+    }
+
+    /// Number of cards listed in the deck excluding padding
+    fn card_count(&self) -> usize {
+        self.cards.iter().map(|entry| entry.count).sum()
+    }
+
+    /// Checks the deck is valid
+    pub(crate) fn validate(&self, name: &str) -> Result<(), String> {
+        if self.card_count() > self.len() {
+            return Err(format!(
+                "{name} deck has {} cards but a size of {}",
+                self.card_count(),
+                self.len()
+            ));
+        }
+
+        let jailcards = self
+            .cards
+            .iter()
+            .filter(|entry| entry.card == Card::GetOutOfJail)
+            .map(|entry| entry.count)
+            .sum::<usize>();
+
+        if jailcards > 1 {
+            return Err(format!("{name} deck has more than one get out of jail free card"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
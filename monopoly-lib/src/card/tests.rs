@@ -0,0 +1,64 @@
+use super::*;
+
+#[test]
+fn test_classic_decks() {
+    let chdeck = DeckDefinition::classic_chance().build_deck();
+
+    assert_eq!(chdeck.len(), 16);
+    assert_eq!(chdeck.iter().filter(|c| **c == Card::Inconsequential).count(), 5);
+
+    let ccdeck = DeckDefinition::classic_community_chest().build_deck();
+
+    assert_eq!(ccdeck.len(), 16);
+    assert_eq!(ccdeck.iter().filter(|c| **c == Card::Inconsequential).count(), 13);
+    assert!(ccdeck.contains(&Card::GetOutOfJail));
+}
+
+#[test]
+fn test_counts() {
+    let deck = DeckDefinition::new(
+        vec![
+            DeckEntry {
+                card: Card::GoJail,
+                count: 2,
+            },
+            DeckEntry {
+                card: Card::Back(2),
+                count: 1,
+            },
+        ],
+        None,
+    );
+
+    assert_eq!(deck.len(), 3);
+    assert_eq!(deck.build_deck(), [Card::GoJail, Card::GoJail, Card::Back(2)]);
+    assert!(deck.validate("Test").is_ok());
+
+    let deck = DeckDefinition::new(deck.cards().to_vec(), Some(2));
+
+    assert!(deck.validate("Test").is_err());
+}
+
+#[test]
+fn test_movement() {
+    let boarddef = BoardDefinition::default();
+    let ch1 = boarddef.find(Space::Chance(0));
+    let ch3 = boarddef.find(Space::Chance(2));
+
+    assert_eq!(Card::GoTo(Space::Go).movement(&boarddef, ch1), CardMove::To(0));
+    assert_eq!(Card::GoJail.movement(&boarddef, ch1), CardMove::Jail);
+    assert_eq!(
+        Card::GoNext(PropertySet::Station).movement(&boarddef, ch3),
+        CardMove::To(boarddef.find(Space::Rail(0)))
+    );
+    assert_eq!(
+        Card::GoNext(PropertySet::Utility).movement(&boarddef, ch1),
+        CardMove::To(boarddef.find(Space::Utility(0)))
+    );
+    assert_eq!(
+        Card::Back(3).movement(&boarddef, ch3),
+        CardMove::To(boarddef.find(Space::CommunityChest(2)))
+    );
+    assert_eq!(Card::GetOutOfJail.movement(&boarddef, ch1), CardMove::Stay);
+    assert_eq!(Card::Inconsequential.movement(&boarddef, ch1), CardMove::Stay);
+}
@@ -0,0 +1,469 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs,
+    path::Path,
+};
+
+use itertools::Itertools;
+use rand::Rng;
+use serde::{Deserialize, Serialize, Serializer, ser::SerializeStruct};
+
+use crate::boarddef::BoardDefinition;
+use crate::calc::probability::Probability;
+use crate::space::Space;
+
+/// Maximum number of main dice
+pub const MAX_DICE: usize = 4;
+
+/// Maximum number of faces on a die
+pub const MAX_FACES: usize = 20;
+
+/// A die with a relative weight for each face. Faces are numbered from 1
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Die {
+    weights: Vec<u32>,
+}
+
+impl Die {
+    /// Create a fair die with a number of sides
+    pub fn fair(sides: u8) -> Self {
+        Self {
+            weights: vec![1; sides as usize],
+        }
+    }
+
+    /// Create a loaded die with a relative weight for each face
+    pub fn loaded(weights: Vec<u32>) -> Self {
+        Self { weights }
+    }
+
+    /// Returns the number of sides
+    pub fn sides(&self) -> u8 {
+        self.weights.len() as u8
+    }
+
+    /// Returns the relative weight of each face
+    pub fn weights(&self) -> &[u32] {
+        &self.weights
+    }
+
+    /// Returns the probability of rolling a value
+    pub fn probability(&self, value: u8) -> Probability {
+        match self.weights.get((value as usize).wrapping_sub(1)) {
+            Some(weight) => Probability::new(*weight as i64, self.total_weight()),
+            None => Probability::NEVER,
+        }
+    }
+
+    /// Returns true if every face is equally likely
+    pub fn is_fair(&self) -> bool {
+        self.weights.iter().all_equal()
+    }
+
+    /// Roll the die
+    fn roll<R: Rng>(&self, rng: &mut R) -> u8 {
+        if self.is_fair() {
+            return rng.gen_range(1..=self.sides());
+        }
+
+        let mut n = rng.gen_range(0..self.total_weight());
+
+        for (i, weight) in self.weights.iter().enumerate() {
+            if n < *weight as u64 {
+                return i as u8 + 1;
+            }
+
+            n -= *weight as u64;
+        }
+
+        unreachable!("Die weights exhausted")
+    }
+
+    /// Returns the sum of the face weights
+    fn total_weight(&self) -> u64 {
+        self.weights.iter().map(|weight| *weight as u64).sum()
+    }
+}
+
+/// Face of the speed die (Mega Monopoly)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SpeedFace {
+    /// Add a number to the total of the main dice
+    Number(u8),
+    /// Move by the larger of the main dice instead of their total
+    Bus,
+    /// Move by the total of the main dice, then advance to the next space which can be bought
+    MrMonopoly,
+}
+
+/// Speed die rolled with the main dice when not in jail. Each face is equally likely and faces can be repeated
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpeedDie {
+    faces: Vec<SpeedFace>,
+}
+
+impl SpeedDie {
+    /// Create a speed die with a list of faces
+    pub fn new(faces: Vec<SpeedFace>) -> Self {
+        Self { faces }
+    }
+
+    /// Create the Mega Monopoly speed die (1, 2, 3, bus and two Mr. Monopoly faces)
+    pub fn mega() -> Self {
+        Self::new(vec![
+            SpeedFace::Number(1),
+            SpeedFace::Number(2),
+            SpeedFace::Number(3),
+            SpeedFace::Bus,
+            SpeedFace::MrMonopoly,
+            SpeedFace::MrMonopoly,
+        ])
+    }
+
+    /// Returns the faces of the die
+    pub fn faces(&self) -> &[SpeedFace] {
+        &self.faces
+    }
+
+    /// Returns each distinct face with its probability
+    pub fn outcomes(&self) -> Vec<(SpeedFace, Probability)> {
+        self.faces
+            .iter()
+            .copied()
+            .counts()
+            .into_iter()
+            .sorted()
+            .map(|(face, count)| (face, Probability::new(count as i64, self.faces.len() as u64)))
+            .collect()
+    }
+
+    /// Roll the die
+    fn roll<R: Rng>(&self, rng: &mut R) -> SpeedFace {
+        self.faces[rng.gen_range(0..self.faces.len())]
+    }
+}
+
+/// Values shown by the dice after a roll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Roll {
+    values: [u8; MAX_DICE],
+    count: u8,
+    speed: Option<SpeedFace>,
+}
+
+impl Roll {
+    /// Create a roll from the values of the main dice and the speed die face if rolled
+    pub fn new(dice: &[u8], speed: Option<SpeedFace>) -> Self {
+        assert!(dice.len() <= MAX_DICE, "Too many dice");
+
+        let mut values = [0; MAX_DICE];
+        values[..dice.len()].copy_from_slice(dice);
+
+        Self {
+            values,
+            count: dice.len() as u8,
+            speed,
+        }
+    }
+
+    /// Returns the values of the main dice
+    pub fn dice(&self) -> &[u8] {
+        &self.values[..self.count as usize]
+    }
+
+    /// Returns the speed die face if rolled
+    pub fn speed(&self) -> Option<SpeedFace> {
+        self.speed
+    }
+
+    /// Returns true if all of the main dice show the same value
+    pub fn double(&self) -> bool {
+        self.dice().iter().all_equal()
+    }
+
+    /// Returns the total of the main dice
+    pub fn dice_total(&self) -> u8 {
+        self.dice().iter().sum()
+    }
+
+    /// Returns the total of the main dice and any number on the speed die. Used for utility rent
+    pub fn total(&self) -> u8 {
+        match self.speed {
+            Some(SpeedFace::Number(n)) => self.dice_total() + n,
+            _ => self.dice_total(),
+        }
+    }
+
+    /// Returns the number of spaces to move before any Mr. Monopoly advance
+    pub fn distance(&self) -> u8 {
+        match self.speed {
+            Some(SpeedFace::Bus) => self.dice().iter().copied().max().unwrap_or(0),
+            _ => self.total(),
+        }
+    }
+}
+
+impl From<(u8, u8)> for Roll {
+    fn from((d1, d2): (u8, u8)) -> Self {
+        Self::new(&[d1, d2], None)
+    }
+}
+
+impl Display for Roll {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for value in self.dice() {
+            match value {
+                1..=6 => write!(f, "{}", ['⚀', '⚁', '⚂', '⚃', '⚄', '⚅'][*value as usize - 1])?,
+                _ => write!(f, "[{value}]")?,
+            }
+        }
+
+        match self.speed {
+            Some(SpeedFace::Number(n)) => write!(f, "+{n}"),
+            Some(SpeedFace::Bus) => write!(f, "+Bus"),
+            Some(SpeedFace::MrMonopoly) => write!(f, "+Mr.M"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Serialize for Roll {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Roll", 2)?;
+
+        state.serialize_field("dice", self.dice())?;
+
+        match self.speed {
+            Some(speed) => state.serialize_field("speed", &speed)?,
+            None => state.skip_field("speed")?,
+        }
+
+        state.end()
+    }
+}
+
+/// The dice rolled each turn. Used by both the simulation and the Markov chain calculation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiceModel {
+    dice: Vec<Die>,
+    #[serde(default)]
+    speed: Option<SpeedDie>,
+}
+
+impl Default for DiceModel {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl DiceModel {
+    /// Creates a dice model with a number of main dice (a double is rolled when they all show the same value) and
+    /// an optional speed die
+    pub fn new(dice: Vec<Die>, speed: Option<SpeedDie>) -> Result<Self, DiceModelError> {
+        let model = Self { dice, speed };
+
+        model.validate()?;
+
+        Ok(model)
+    }
+
+    /// Two fair six sided dice
+    pub fn classic() -> Self {
+        Self {
+            dice: vec![Die::fair(6), Die::fair(6)],
+            speed: None,
+        }
+    }
+
+    /// Two fair six sided dice and the speed die (Mega Monopoly)
+    pub fn mega() -> Self {
+        Self {
+            dice: vec![Die::fair(6), Die::fair(6)],
+            speed: Some(SpeedDie::mega()),
+        }
+    }
+
+    /// Load a dice model from a file. Files with a .json extension are parsed as JSON, all others as TOML
+    pub fn load(file: &Path) -> Result<Self, DiceModelError> {
+        let contents = fs::read_to_string(file).map_err(DiceModelError::Io)?;
+
+        match file.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json(&contents),
+            _ => Self::from_toml(&contents),
+        }
+    }
+
+    /// Parse a dice model from a TOML string
+    pub fn from_toml(toml: &str) -> Result<Self, DiceModelError> {
+        let model: Self = toml::from_str(toml).map_err(DiceModelError::Toml)?;
+
+        model.validate()?;
+
+        Ok(model)
+    }
+
+    /// Parse a dice model from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, DiceModelError> {
+        let model: Self = serde_json::from_str(json).map_err(DiceModelError::Json)?;
+
+        model.validate()?;
+
+        Ok(model)
+    }
+
+    /// Returns the main dice
+    pub fn dice(&self) -> &[Die] {
+        &self.dice
+    }
+
+    /// Returns the speed die if used
+    pub fn speed(&self) -> Option<&SpeedDie> {
+        self.speed.as_ref()
+    }
+
+    /// Returns every possible roll with its probability, ordered by the total of the main dice. The speed die is
+    /// rolled unless in jail
+    pub fn rolls(&self, in_jail: bool) -> Vec<(Roll, Probability)> {
+        let speed = match &self.speed {
+            Some(speed) if !in_jail => speed.outcomes().into_iter().map(|(face, p)| (Some(face), p)).collect(),
+            _ => vec![(None, Probability::ALWAYS)],
+        };
+
+        self.dice
+            .iter()
+            .map(|die| 1..=die.sides())
+            .multi_cartesian_product()
+            .sorted_by(|a, b| a.iter().sum::<u8>().cmp(&b.iter().sum::<u8>()).then_with(|| a.cmp(b)))
+            .filter_map(|values| {
+                let prob = self
+                    .dice
+                    .iter()
+                    .zip(&values)
+                    .map(|(die, value)| die.probability(*value))
+                    .fold(Probability::ALWAYS, |acc, p| acc * p);
+
+                (prob != Probability::NEVER).then_some((values, prob))
+            })
+            .cartesian_product(speed)
+            .map(|((values, prob), (face, face_prob))| (Roll::new(&values, face), prob * face_prob))
+            .collect()
+    }
+
+    /// Returns the probability of rolling a double
+    pub fn double_probability(&self) -> Probability {
+        self.rolls(true)
+            .into_iter()
+            .filter(|(roll, _)| roll.double())
+            .map(|(_, prob)| prob)
+            .sum()
+    }
+
+    /// Returns the largest possible roll total
+    pub fn max_total(&self) -> u8 {
+        let speed = self
+            .speed
+            .iter()
+            .flat_map(|speed| speed.faces())
+            .map(|face| match face {
+                SpeedFace::Number(n) => *n,
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0);
+
+        self.dice.iter().map(|die| die.sides()).sum::<u8>() + speed
+    }
+
+    /// Returns the space moved to from a position for a roll
+    pub fn destination(&self, boarddef: &BoardDefinition, position: usize, roll: &Roll) -> usize {
+        let spaces = boarddef.len();
+        let elem = (position + roll.distance() as usize) % spaces;
+
+        if roll.speed() == Some(SpeedFace::MrMonopoly) && boarddef[elem] != Space::GoToJail {
+            // Advance to the next space which can be bought (ownership is not considered)
+            (1..spaces)
+                .map(|i| (elem + i) % spaces)
+                .find(|&next| boarddef[next].price().is_some())
+                .unwrap_or(elem)
+        } else {
+            elem
+        }
+    }
+
+    /// Roll the dice. The speed die is rolled unless in jail
+    pub fn roll<R: Rng>(&self, rng: &mut R, in_jail: bool) -> Roll {
+        let mut values = [0; MAX_DICE];
+
+        for (value, die) in values.iter_mut().zip(&self.dice) {
+            *value = die.roll(rng);
+        }
+
+        let speed = match &self.speed {
+            Some(speed) if !in_jail => Some(speed.roll(rng)),
+            _ => None,
+        };
+
+        Roll::new(&values[..self.dice.len()], speed)
+    }
+
+    /// Check the dice are valid
+    fn validate(&self) -> Result<(), DiceModelError> {
+        let invalid = |msg: &str| Err(DiceModelError::Invalid(msg.to_string()));
+
+        if !(2..=MAX_DICE).contains(&self.dice.len()) {
+            return invalid(&format!("Between 2 and {MAX_DICE} main dice are required"));
+        }
+
+        for die in &self.dice {
+            if !(1..=MAX_FACES).contains(&die.weights.len()) {
+                return invalid(&format!("Dice must have between 1 and {MAX_FACES} faces"));
+            }
+
+            if die.total_weight() == 0 {
+                return invalid("Dice must have a face with a non-zero weight");
+            }
+        }
+
+        if let Some(speed) = &self.speed {
+            if speed.faces.is_empty() {
+                return invalid("The speed die has no faces");
+            }
+
+            if speed
+                .faces
+                .iter()
+                .any(|face| matches!(face, SpeedFace::Number(n) if !(1..=MAX_FACES as u8).contains(n)))
+            {
+                return invalid(&format!("Speed die numbers must be between 1 and {MAX_FACES}"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Dice model load error
+#[derive(Debug)]
+pub enum DiceModelError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl Display for DiceModelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiceModelError::Io(e) => write!(f, "Unable to read dice model: {e}"),
+            DiceModelError::Toml(e) => write!(f, "Unable to parse dice model: {e}"),
+            DiceModelError::Json(e) => write!(f, "Unable to parse dice model: {e}"),
+            DiceModelError::Invalid(msg) => write!(f, "Invalid dice model: {msg}"),
+        }
+    }
+}
+
+impl Error for DiceModelError {}
+
+#[cfg(test)]
+mod tests;
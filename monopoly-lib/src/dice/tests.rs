@@ -0,0 +1,124 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use super::*;
+
+#[test]
+fn test_classic() {
+    let dice = DiceModel::classic();
+    let rolls = dice.rolls(false);
+
+    assert_eq!(rolls.len(), 36);
+    assert_eq!(rolls.iter().filter(|(roll, _)| roll.double()).count(), 6);
+    assert_eq!(rolls.iter().map(|(_, p)| *p).sum::<Probability>(), Probability::ALWAYS);
+    assert!(rolls.iter().all(|(_, p)| *p == Probability::new(1, 36)));
+    assert!(rolls.windows(2).all(|w| w[0].0.total() <= w[1].0.total()));
+    assert_eq!(dice.double_probability(), Probability::new(1, 6));
+    assert_eq!(dice.max_total(), 12);
+}
+
+#[test]
+fn test_loaded() {
+    let dice = DiceModel::new(vec![Die::loaded(vec![1, 1, 1, 1, 1, 3]), Die::fair(6)], None).unwrap();
+
+    assert_eq!(dice.double_probability(), Probability::new(1, 6));
+    assert_eq!(dice.dice()[0].probability(6), Probability::new(3, 8));
+    assert_eq!(dice.dice()[0].probability(7), Probability::NEVER);
+
+    let dice = DiceModel::new(vec![Die::loaded(vec![1, 0, 1]), Die::loaded(vec![1, 0, 1])], None).unwrap();
+
+    // Faces which can't be rolled are left out
+    assert_eq!(dice.rolls(false).len(), 4);
+    assert_eq!(dice.double_probability(), Probability::new(1, 2));
+
+    // Sampled rolls follow the weights
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+    for _ in 0..100 {
+        let roll = dice.roll(&mut rng, false);
+
+        assert!(roll.dice().iter().all(|value| *value == 1 || *value == 3));
+    }
+}
+
+#[test]
+fn test_three_dice() {
+    let dice = DiceModel::new(vec![Die::fair(4), Die::fair(4), Die::fair(4)], None).unwrap();
+    let rolls = dice.rolls(false);
+
+    assert_eq!(rolls.len(), 64);
+    assert_eq!(dice.double_probability(), Probability::new(1, 16));
+    assert_eq!(dice.max_total(), 12);
+    assert_eq!(rolls[0].0.total(), 3);
+}
+
+#[test]
+fn test_speed_die() {
+    let boarddef = BoardDefinition::default();
+    let dice = DiceModel::mega();
+
+    // Distinct speed faces are combined, and the speed die isn't rolled in jail
+    assert_eq!(dice.rolls(false).len(), 36 * 5);
+    assert_eq!(dice.rolls(true).len(), 36);
+    assert_eq!(
+        dice.rolls(false).iter().map(|(_, p)| *p).sum::<Probability>(),
+        Probability::ALWAYS
+    );
+    assert_eq!(dice.double_probability(), Probability::new(1, 6));
+    assert_eq!(dice.max_total(), 15);
+
+    // Number adds to the total
+    let roll = Roll::new(&[2, 3], Some(SpeedFace::Number(3)));
+    assert_eq!(roll.total(), 8);
+    assert_eq!(dice.destination(&boarddef, 0, &roll), 8);
+
+    // Bus moves by the larger die
+    let roll = Roll::new(&[2, 3], Some(SpeedFace::Bus));
+    assert_eq!(roll.total(), 5);
+    assert_eq!(dice.destination(&boarddef, 0, &roll), 3);
+
+    // Mr. Monopoly advances to the next property after the move (CC1 -> Baltic Avenue)
+    let roll = Roll::new(&[1, 1], Some(SpeedFace::MrMonopoly));
+    assert_eq!(dice.destination(&boarddef, 0, &roll), 3);
+
+    // Mr. Monopoly doesn't move on from go to jail
+    let roll = Roll::new(&[4, 6], Some(SpeedFace::MrMonopoly));
+    assert_eq!(dice.destination(&boarddef, 20, &roll), 30);
+
+    assert!(dice.roll(&mut ChaCha8Rng::seed_from_u64(1), true).speed().is_none());
+    assert!(dice.roll(&mut ChaCha8Rng::seed_from_u64(1), false).speed().is_some());
+}
+
+#[test]
+fn test_load() {
+    let dice = DiceModel::from_toml(include_str!("../../dice/mega.toml")).unwrap();
+
+    assert_eq!(dice, DiceModel::mega());
+    assert!(dice.dice()[0].is_fair());
+
+    let dice = DiceModel::from_toml("[[dice]]\nweights = [1, 1, 1, 1, 1, 2]\n[[dice]]\nweights = [1, 1, 1, 1, 1, 1]\n")
+        .unwrap();
+
+    assert!(!dice.dice()[0].is_fair());
+    assert!(dice.speed().is_none());
+
+    let json = serde_json::to_string(&DiceModel::mega()).unwrap();
+    assert_eq!(DiceModel::from_json(&json).unwrap(), DiceModel::mega());
+
+    assert!(matches!(
+        DiceModel::new(vec![Die::fair(6)], None),
+        Err(DiceModelError::Invalid(_))
+    ));
+    assert!(matches!(
+        DiceModel::new(vec![Die::fair(6), Die::loaded(vec![0, 0])], None),
+        Err(DiceModelError::Invalid(_))
+    ));
+    assert!(matches!(
+        DiceModel::new(vec![Die::fair(6), Die::fair(6)], Some(SpeedDie::new(vec![]))),
+        Err(DiceModelError::Invalid(_))
+    ));
+    assert!(matches!(
+        DiceModel::from_toml("[[dice]]\nweights = [1]\n"),
+        Err(DiceModelError::Invalid(_))
+    ));
+}
@@ -1,6 +1,8 @@
-pub mod chance;
-pub mod commchest;
+pub mod boarddef;
+pub mod card;
+pub mod dice;
 pub mod movereason;
+pub mod rules;
 pub mod space;
 pub mod strategy;
 
@@ -1,8 +1,9 @@
 use num_derive::FromPrimitive;
+use serde::Serialize;
 pub use strum::IntoEnumIterator;
 use strum::{EnumCount, EnumIter};
 
-#[derive(Debug, PartialEq, Eq, FromPrimitive, EnumCount, EnumIter, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, FromPrimitive, EnumCount, EnumIter, Clone, Copy, Serialize)]
 pub enum MoveReason {
     Roll = -1,        // Normal roll
     CHCard = 0,       // Chance card
@@ -0,0 +1,177 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::boarddef::BoardDefinition;
+use crate::card::{Card, CardMove};
+use crate::space::Space;
+use crate::strategy::JAIL_ATTEMPTS;
+
+/// What happens when a card moves a player back past go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BackPastGo {
+    /// Carry on round the board without collecting salary
+    #[default]
+    Wrap,
+    /// Carry on round the board and collect salary
+    Salary,
+    /// Stop on go
+    Stop,
+}
+
+/// Game rule variants (house rules). Both the simulation and the Markov chain calculation honour the rules which
+/// affect movement, the rest only apply when playing for money
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Taxes and jail fines are paid into a pot which is won by landing on free parking
+    pub free_parking_pot: bool,
+    /// Salary is doubled for landing exactly on go
+    pub double_go_salary: bool,
+    /// Rolling three doubles in a row sends the player to jail. Otherwise the third double ends the turn
+    pub triple_double_jail: bool,
+    /// Number of failed attempts to roll a double before the fine must be paid to leave jail (1 to 3)
+    pub jail_attempts: u8,
+    /// What happens when a card moves a player back past go
+    pub back_past_go: BackPastGo,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl Rules {
+    /// The official rules
+    pub const fn classic() -> Self {
+        Self {
+            free_parking_pot: false,
+            double_go_salary: false,
+            triple_double_jail: true,
+            jail_attempts: JAIL_ATTEMPTS,
+            back_past_go: BackPastGo::Wrap,
+        }
+    }
+
+    /// Load rules from a file. Files with a .json extension are parsed as JSON, all others as TOML
+    pub fn load(file: &Path) -> Result<Self, RulesError> {
+        let contents = fs::read_to_string(file).map_err(RulesError::Io)?;
+
+        match file.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json(&contents),
+            _ => Self::from_toml(&contents),
+        }
+    }
+
+    /// Parse rules from a TOML string
+    pub fn from_toml(toml: &str) -> Result<Self, RulesError> {
+        let rules: Self = toml::from_str(toml).map_err(RulesError::Toml)?;
+
+        rules.validate()?;
+
+        Ok(rules)
+    }
+
+    /// Parse rules from a JSON string
+    pub fn from_json(json: &str) -> Result<Self, RulesError> {
+        let rules: Self = serde_json::from_str(json).map_err(RulesError::Json)?;
+
+        rules.validate()?;
+
+        Ok(rules)
+    }
+
+    /// Check the rules are valid
+    pub fn validate(&self) -> Result<(), RulesError> {
+        if !(1..=JAIL_ATTEMPTS).contains(&self.jail_attempts) {
+            return Err(RulesError::Invalid(format!(
+                "Jail attempts must be between 1 and {JAIL_ATTEMPTS}"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Calculates the movement caused by drawing a card at a given position
+    pub fn card_movement(&self, card: Card, boarddef: &BoardDefinition, position: usize) -> CardMove {
+        match (card, card.movement(boarddef, position)) {
+            (Card::Back(_), CardMove::To(elem)) if elem > position && self.back_past_go == BackPastGo::Stop => {
+                CardMove::To(boarddef.find(Space::Go))
+            }
+            (_, movement) => movement,
+        }
+    }
+
+    /// Returns true if salary is collected for a card moving a player from one space to another
+    pub fn card_passes_go(&self, card: Card, position: usize, elem: usize) -> bool {
+        match card {
+            Card::Back(_) => elem > position && self.back_past_go == BackPastGo::Salary,
+            _ => elem < position,
+        }
+    }
+}
+
+impl Display for Rules {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut rules = Vec::new();
+
+        if self.free_parking_pot {
+            rules.push("free parking pot".to_string());
+        }
+
+        if self.double_go_salary {
+            rules.push("double salary on go".to_string());
+        }
+
+        if !self.triple_double_jail {
+            rules.push("no jail for three doubles".to_string());
+        }
+
+        if self.jail_attempts != JAIL_ATTEMPTS {
+            rules.push(format!("{} attempts to leave jail", self.jail_attempts));
+        }
+
+        match self.back_past_go {
+            BackPastGo::Wrap => (),
+            BackPastGo::Salary => rules.push("salary when moving back past go".to_string()),
+            BackPastGo::Stop => rules.push("stop on go when moving back".to_string()),
+        }
+
+        if rules.is_empty() {
+            write!(f, "classic")
+        } else {
+            write!(f, "{}", rules.join(", "))
+        }
+    }
+}
+
+/// Rules load error
+#[derive(Debug)]
+pub enum RulesError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl Display for RulesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RulesError::Io(e) => write!(f, "Unable to read rules: {e}"),
+            RulesError::Toml(e) => write!(f, "Unable to parse rules: {e}"),
+            RulesError::Json(e) => write!(f, "Unable to parse rules: {e}"),
+            RulesError::Invalid(msg) => write!(f, "Invalid rules: {msg}"),
+        }
+    }
+}
+
+impl Error for RulesError {}
+
+#[cfg(test)]
+mod tests;
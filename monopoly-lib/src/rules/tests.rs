@@ -0,0 +1,70 @@
+use super::*;
+
+#[test]
+fn test_load() {
+    assert_eq!(Rules::from_toml("").unwrap(), Rules::classic());
+    assert_eq!(Rules::classic().to_string(), "classic");
+
+    let house = Rules::from_toml(include_str!("../../rules/house.toml")).unwrap();
+
+    assert_eq!(
+        house.to_string(),
+        "free parking pot, double salary on go, no jail for three doubles, 2 attempts to leave jail"
+    );
+
+    let rules = Rules::from_toml("free_parking_pot = true\njail_attempts = 2\nback_past_go = \"Stop\"\n").unwrap();
+
+    assert!(rules.free_parking_pot);
+    assert!(rules.triple_double_jail);
+    assert_eq!(rules.jail_attempts, 2);
+    assert_eq!(rules.back_past_go, BackPastGo::Stop);
+    assert_eq!(
+        rules.to_string(),
+        "free parking pot, 2 attempts to leave jail, stop on go when moving back"
+    );
+
+    let json = serde_json::to_string(&rules).unwrap();
+    assert_eq!(Rules::from_json(&json).unwrap(), rules);
+
+    assert!(matches!(
+        Rules::from_toml("jail_attempts = 0"),
+        Err(RulesError::Invalid(_))
+    ));
+    assert!(matches!(
+        Rules::from_toml("jail_attempts = 4"),
+        Err(RulesError::Invalid(_))
+    ));
+}
+
+#[test]
+fn test_back_past_go() {
+    let boarddef = BoardDefinition::default();
+    let end = boarddef.len() - 1;
+
+    let wrap = Rules::classic();
+    let salary = Rules {
+        back_past_go: BackPastGo::Salary,
+        ..Rules::classic()
+    };
+    let stop = Rules {
+        back_past_go: BackPastGo::Stop,
+        ..Rules::classic()
+    };
+
+    // Moving back without passing go
+    for rules in [wrap, salary, stop] {
+        assert_eq!(rules.card_movement(Card::Back(3), &boarddef, 7), CardMove::To(4));
+        assert!(!rules.card_passes_go(Card::Back(3), 7, 4));
+    }
+
+    // Moving back past go
+    assert_eq!(wrap.card_movement(Card::Back(3), &boarddef, 1), CardMove::To(end - 1));
+    assert_eq!(salary.card_movement(Card::Back(3), &boarddef, 1), CardMove::To(end - 1));
+    assert_eq!(stop.card_movement(Card::Back(3), &boarddef, 1), CardMove::To(0));
+
+    assert!(!wrap.card_passes_go(Card::Back(3), 1, end - 1));
+    assert!(salary.card_passes_go(Card::Back(3), 1, end - 1));
+
+    // Advancing past go always collects salary
+    assert!(wrap.card_passes_go(Card::GoTo(Space::Go), 36, 0));
+}
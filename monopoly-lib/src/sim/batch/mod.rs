@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+
+/// Number of moves in each batch to start with
+const INITIAL_BATCH_MOVES: u64 = 64;
+
+/// Adjacent batches are merged when this many are complete, doubling the batch size
+const MAX_BATCHES: usize = 64;
+
+/// Minimum number of complete batches needed to estimate a confidence interval
+const MIN_BATCHES: usize = MAX_BATCHES / 2;
+
+/// Arrival counts for batches of consecutive moves. Moves in a single game aren't independent so the variance of the
+/// arrival frequencies is estimated from the spread of the batch frequencies (batch means) instead. The number of
+/// batches is kept between 32 and 64 by merging adjacent batches as the simulation runs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Batches {
+    size: u64,
+    complete: Vec<Vec<u64>>,
+    current: Vec<u64>,
+    current_moves: u64,
+}
+
+/// Estimated arrival frequency with its confidence interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceInterval {
+    /// Arrival frequency per move
+    pub estimate: f64,
+    /// Standard error of the estimate
+    pub std_error: f64,
+    /// Lower bound of the interval
+    pub lower: f64,
+    /// Upper bound of the interval
+    pub upper: f64,
+}
+
+impl ConfidenceInterval {
+    /// Returns half the width of the interval
+    pub fn half_width(&self) -> f64 {
+        (self.upper - self.lower) / 2.0
+    }
+
+    /// Returns true if a value lies within the interval
+    pub fn contains(&self, value: f64) -> bool {
+        value >= self.lower && value <= self.upper
+    }
+}
+
+impl Batches {
+    /// Create empty batches for a board with a given number of spaces
+    pub fn new(spaces: usize) -> Self {
+        Self {
+            size: INITIAL_BATCH_MOVES,
+            complete: Vec::with_capacity(MAX_BATCHES),
+            current: vec![0; spaces],
+            current_moves: 0,
+        }
+    }
+
+    /// Returns the number of moves in each complete batch
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the number of complete batches
+    pub fn len(&self) -> usize {
+        self.complete.len()
+    }
+
+    /// Returns true if no batches are complete
+    pub fn is_empty(&self) -> bool {
+        self.complete.is_empty()
+    }
+
+    /// Returns the standard error of the arrival frequency for any of a set of spaces. Returns None if too few
+    /// batches are complete
+    pub fn std_error(&self, elems: &[usize]) -> Option<f64> {
+        if self.complete.len() < MIN_BATCHES {
+            return None;
+        }
+
+        let batches = self.complete.len() as f64;
+
+        let freqs = self
+            .complete
+            .iter()
+            .map(|batch| elems.iter().map(|elem| batch[*elem]).sum::<u64>() as f64 / self.size as f64)
+            .collect::<Vec<_>>();
+
+        let mean = freqs.iter().sum::<f64>() / batches;
+        let variance = freqs.iter().map(|f| (f - mean).powi(2)).sum::<f64>() / (batches - 1.0);
+
+        Some((variance / batches).sqrt())
+    }
+
+    /// Returns the quantile of the Student's t distribution to use for a confidence level with the current number
+    /// of batches
+    pub fn t_quantile(&self, confidence: f64) -> f64 {
+        t_quantile(0.5 + confidence / 2.0, self.complete.len().max(2) as f64 - 1.0)
+    }
+
+    /// Merge batches from an independent simulation. The batch sizes are matched by merging adjacent batches. The
+    /// incomplete batch of the other simulation is dropped
+    pub fn merge(&mut self, other: &Batches) {
+        let mut other = other.clone();
+
+        while self.size < other.size {
+            self.coarsen();
+        }
+
+        while other.size < self.size {
+            other.coarsen();
+        }
+
+        self.complete.extend(other.complete);
+
+        while self.complete.len() >= MAX_BATCHES {
+            self.coarsen();
+        }
+    }
+
+    /// Record an arrival on a space
+    pub(super) fn record_arrival(&mut self, elem: usize) {
+        self.current[elem] += 1;
+        self.current_moves += 1;
+
+        if self.current_moves == self.size {
+            // Batch complete
+            let spaces = self.current.len();
+
+            self.complete
+                .push(std::mem::replace(&mut self.current, vec![0; spaces]));
+            self.current_moves = 0;
+
+            if self.complete.len() == MAX_BATCHES {
+                self.coarsen();
+            }
+        }
+    }
+
+    /// Merge adjacent batches, doubling the batch size. A batch left over at the end is dropped
+    fn coarsen(&mut self) {
+        self.complete = self
+            .complete
+            .chunks_exact(2)
+            .map(|pair| pair[0].iter().zip(&pair[1]).map(|(a, b)| a + b).collect())
+            .collect();
+
+        self.size *= 2;
+    }
+}
+
+/// Calculates the quantile of the standard normal distribution (Acklam's approximation)
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.024_25;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Calculates the quantile of the Student's t distribution from the normal quantile (Cornish-Fisher expansion)
+fn t_quantile(p: f64, df: f64) -> f64 {
+    let z = normal_quantile(p);
+    let z2 = z * z;
+
+    let g1 = (z2 + 1.0) * z / 4.0;
+    let g2 = ((5.0 * z2 + 16.0) * z2 + 3.0) * z / 96.0;
+    let g3 = (((3.0 * z2 + 19.0) * z2 + 17.0) * z2 - 15.0) * z / 384.0;
+    let g4 = ((((79.0 * z2 + 776.0) * z2 + 1482.0) * z2 - 1920.0) * z2 - 945.0) * z / 92160.0;
+
+    z + g1 / df + g2 / df.powi(2) + g3 / df.powi(3) + g4 / df.powi(4)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn test_quantiles() {
+    assert!((normal_quantile(0.975) - 1.959_964).abs() < 1e-6);
+    assert!((normal_quantile(0.01) + 2.326_348).abs() < 1e-6);
+    assert!((t_quantile(0.975, 31.0) - 2.039_513).abs() < 1e-5);
+    assert!((t_quantile(0.995, 63.0) - 2.656_145).abs() < 1e-4);
+}
+
+#[test]
+fn test_batches() {
+    let mut batches = Batches::new(2);
+
+    // Alternate between the spaces
+    for i in 0..(INITIAL_BATCH_MOVES * MAX_BATCHES as u64) {
+        batches.record_arrival((i % 2) as usize);
+    }
+
+    // Batches merged on reaching the maximum
+    assert_eq!(batches.len(), MIN_BATCHES);
+    assert_eq!(batches.size(), INITIAL_BATCH_MOVES * 2);
+
+    // Every batch is the same so there is no variance
+    assert_eq!(batches.std_error(&[0]), Some(0.0));
+    assert_eq!(batches.std_error(&[0, 1]), Some(0.0));
+
+    // Merge with batches of a different size
+    let mut other = Batches::new(2);
+
+    for i in 0..(INITIAL_BATCH_MOVES * 5 + 1) {
+        other.record_arrival((i % 2) as usize);
+    }
+
+    other.merge(&batches);
+
+    assert_eq!(other.size(), INITIAL_BATCH_MOVES * 2);
+    assert_eq!(other.len(), MIN_BATCHES + 2);
+    assert_eq!(other.current_moves, 1);
+}
@@ -0,0 +1,876 @@
+use rand::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::Board;
+use super::event::Event;
+use crate::boarddef::BoardDefinition;
+use crate::card::Card;
+use crate::space::{PropertySet, Rent, Space};
+use crate::strategy::{GameView, Trade};
+
+/// Salary collected when passing go
+pub const GO_SALARY: u32 = 200;
+
+/// Fine paid to leave jail
+pub const JAIL_FINE: u32 = 50;
+
+/// Number of buildings which represents a hotel
+pub const HOTEL: u8 = 5;
+
+/// Number of houses held by the bank
+pub const BANK_HOUSES: u32 = 32;
+
+/// Number of hotels held by the bank
+pub const BANK_HOTELS: u32 = 12;
+
+/// Ownership of and buildings on each space on the board
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Economy {
+    pub(super) owner: Vec<Option<usize>>,
+    pub(super) buildings: Vec<u8>,
+    pub(super) mortgaged: Vec<bool>,
+    pub(super) pot: u32,
+}
+
+impl Economy {
+    /// Create a new economy with all spaces owned by the bank
+    pub(super) fn new(spaces: usize) -> Self {
+        Self {
+            owner: vec![None; spaces],
+            buildings: vec![0; spaces],
+            mortgaged: vec![false; spaces],
+            pot: 0,
+        }
+    }
+
+    /// Returns the cash in the free parking pot
+    pub fn pot(&self) -> u32 {
+        self.pot
+    }
+
+    /// Returns the player owning a space
+    pub fn owner(&self, elem: usize) -> Option<usize> {
+        self.owner[elem]
+    }
+
+    /// Returns the number of buildings on a space (5 is a hotel)
+    pub fn buildings(&self, elem: usize) -> u8 {
+        self.buildings[elem]
+    }
+
+    /// Returns true if a space is mortgaged
+    pub fn is_mortgaged(&self, elem: usize) -> bool {
+        self.mortgaged[elem]
+    }
+
+    /// Returns the cash raised by mortgaging a space (half its price)
+    pub fn mortgage_value(&self, boarddef: &BoardDefinition, elem: usize) -> u32 {
+        boarddef[elem].price().unwrap_or(0) / 2
+    }
+
+    /// Returns the cost of paying off the mortgage on a space (the mortgage value plus 10% interest)
+    pub fn unmortgage_cost(&self, boarddef: &BoardDefinition, elem: usize) -> u32 {
+        let value = self.mortgage_value(boarddef, elem);
+
+        value + value.div_ceil(10)
+    }
+
+    /// Returns the interest paid by the new owner when a space changes hands (10% of the mortgage value if it is
+    /// mortgaged, otherwise nothing)
+    pub fn transfer_interest(&self, boarddef: &BoardDefinition, elem: usize) -> u32 {
+        if self.mortgaged[elem] {
+            self.unmortgage_cost(boarddef, elem) - self.mortgage_value(boarddef, elem)
+        } else {
+            0
+        }
+    }
+
+    /// Returns the total interest paid by the new owner when spaces change hands
+    fn transfer_interests(&self, boarddef: &BoardDefinition, spaces: &[usize]) -> u32 {
+        spaces.iter().map(|&elem| self.transfer_interest(boarddef, elem)).sum()
+    }
+
+    /// Returns true if a player can mortgage a space. Buildings on the set must be sold first
+    pub fn can_mortgage(&self, boarddef: &BoardDefinition, player: usize, elem: usize) -> bool {
+        self.owner[elem] == Some(player)
+            && !self.mortgaged[elem]
+            && boarddef[elem].price().is_some()
+            && Self::in_set(boarddef, boarddef[elem].set()).all(|other| self.buildings[other] == 0)
+    }
+
+    /// Returns true if a player can pay off the mortgage on a space
+    pub fn can_unmortgage(&self, player: usize, elem: usize) -> bool {
+        self.owner[elem] == Some(player) && self.mortgaged[elem]
+    }
+
+    /// Returns the positions of the spaces owned by a player
+    pub fn owned_by(&self, player: usize) -> impl Iterator<Item = usize> + '_ {
+        self.owner
+            .iter()
+            .enumerate()
+            .filter(move |(_, owner)| **owner == Some(player))
+            .map(|(elem, _)| elem)
+    }
+
+    /// Returns the number of spaces in a set owned by a player
+    pub fn owned_in_set(&self, boarddef: &BoardDefinition, player: usize, set: PropertySet) -> usize {
+        self.owned_by(player)
+            .filter(|&elem| boarddef[elem].set() == set)
+            .count()
+    }
+
+    /// Returns true if a player owns every space in a set
+    pub fn owns_set(&self, boarddef: &BoardDefinition, player: usize, set: PropertySet) -> bool {
+        let mut spaces = Self::in_set(boarddef, set).peekable();
+
+        spaces.peek().is_some() && spaces.all(|elem| self.owner[elem] == Some(player))
+    }
+
+    /// Returns the number of houses built on the board (hotels not included)
+    pub fn houses_built(&self) -> u32 {
+        self.buildings
+            .iter()
+            .filter(|&&buildings| buildings < HOTEL)
+            .map(|&buildings| buildings as u32)
+            .sum()
+    }
+
+    /// Returns the number of hotels built on the board
+    pub fn hotels_built(&self) -> u32 {
+        self.buildings.iter().filter(|&&buildings| buildings == HOTEL).count() as u32
+    }
+
+    /// Returns the number of houses left in the bank
+    pub fn houses_available(&self) -> u32 {
+        BANK_HOUSES.saturating_sub(self.houses_built())
+    }
+
+    /// Returns the number of hotels left in the bank
+    pub fn hotels_available(&self) -> u32 {
+        BANK_HOTELS.saturating_sub(self.hotels_built())
+    }
+
+    /// Returns true if a player can build on a space. The whole set must be owned and unmortgaged, houses built
+    /// evenly and the bank must have a house (or a hotel to replace four houses) left
+    pub fn can_build(&self, boarddef: &BoardDefinition, player: usize, elem: usize) -> bool {
+        let space = boarddef[elem];
+
+        if space.house_cost().is_none() || self.owner[elem] != Some(player) || self.buildings[elem] >= HOTEL {
+            return false;
+        }
+
+        let in_bank = if self.buildings[elem] == HOTEL - 1 {
+            self.hotels_available()
+        } else {
+            self.houses_available()
+        };
+
+        let set = space.set();
+
+        in_bank > 0
+            && self.owns_set(boarddef, player, set)
+            && Self::in_set(boarddef, set)
+                .all(|other| !self.mortgaged[other] && self.buildings[other] >= self.buildings[elem])
+    }
+
+    /// Returns true if a player can sell a building on a space back to the bank. Buildings must be sold evenly
+    pub fn can_sell(&self, boarddef: &BoardDefinition, player: usize, elem: usize) -> bool {
+        self.owner[elem] == Some(player)
+            && self.buildings[elem] > 0
+            && Self::in_set(boarddef, boarddef[elem].set()).all(|other| self.buildings[other] <= self.buildings[elem])
+    }
+
+    /// Returns the number of buildings left on a space after selling one. A hotel is replaced by four houses, or
+    /// as many as the bank has left
+    pub fn buildings_after_sale(&self, elem: usize) -> u8 {
+        match self.buildings[elem] {
+            HOTEL => (HOTEL - 1).min(self.houses_available() as u8),
+            buildings => buildings.saturating_sub(1),
+        }
+    }
+
+    /// Calculates the rent payable after a card moves a player to the next station (double rent) or utility (the
+    /// highest multiplier times a fresh dice roll total)
+    pub fn card_rent(&self, boarddef: &BoardDefinition, elem: usize, card: Card, dice: u8) -> u32 {
+        let rent = self.rent(boarddef, elem, dice);
+
+        if rent == 0 {
+            // Unowned or mortgaged
+            return 0;
+        }
+
+        match (card, boarddef[elem].rent()) {
+            (Card::GoNext(_), Some(Rent::Station(_))) => rent * 2,
+            (Card::GoNext(_), Some(Rent::Utility(mult))) => mult[mult.len() - 1] * dice as u32,
+            _ => rent,
+        }
+    }
+
+    /// Returns true if a player can trade a space. Spaces can't be traded while their set has buildings on it
+    pub fn can_trade(&self, boarddef: &BoardDefinition, player: usize, elem: usize) -> bool {
+        self.owner[elem] == Some(player)
+            && Self::in_set(boarddef, boarddef[elem].set()).all(|other| self.buildings[other] == 0)
+    }
+
+    /// Returns the positions of the spaces in a set
+    fn in_set(boarddef: &BoardDefinition, set: PropertySet) -> impl Iterator<Item = usize> {
+        (0..boarddef.len()).filter(move |&elem| boarddef[elem].set() == set)
+    }
+
+    /// Calculates the rent payable for landing on a space with a given dice roll total. No rent is payable on
+    /// mortgaged spaces
+    pub fn rent(&self, boarddef: &BoardDefinition, elem: usize, dice: u8) -> u32 {
+        let Some(owner) = self.owner[elem] else {
+            return 0;
+        };
+
+        if self.mortgaged[elem] {
+            return 0;
+        }
+
+        let space = boarddef[elem];
+
+        match space.rent() {
+            Some(Rent::Property(rent)) => {
+                let buildings = self.buildings[elem] as usize;
+
+                if buildings == 0 && self.owns_set(boarddef, owner, space.set()) {
+                    // Rent is doubled on undeveloped streets when the whole set is owned
+                    rent[0] * 2
+                } else {
+                    rent[buildings]
+                }
+            }
+            Some(Rent::Station(rent)) => {
+                let owned = self.owned_in_set(boarddef, owner, PropertySet::Station);
+
+                rent[owned.min(rent.len()) - 1]
+            }
+            Some(Rent::Utility(mult)) => {
+                let owned = self.owned_in_set(boarddef, owner, PropertySet::Utility);
+
+                mult[owned.min(mult.len()) - 1] * dice as u32
+            }
+            None => 0,
+        }
+    }
+}
+
+impl<R: RngCore + SeedableRng> Board<R> {
+    /// Start playing for money, giving each player an amount of starting cash
+    pub fn enable_economy(&mut self, cash: u32) {
+        self.economy = Some(Economy::new(self.boarddef.len()));
+
+        for player in &mut self.players {
+            player.cash = cash;
+            player.bankrupt = false;
+        }
+    }
+
+    /// Returns the property ownership if playing for money
+    pub fn economy(&self) -> Option<&Economy> {
+        self.economy.as_ref()
+    }
+
+    /// Returns the number of players who are not bankrupt
+    pub fn active_players(&self) -> usize {
+        self.players.iter().filter(|player| !player.bankrupt).count()
+    }
+
+    /// Returns true if playing for money and only one player is left (or a lone player is bankrupt)
+    pub fn game_over(&self) -> bool {
+        self.economy.is_some() && self.active_players() < self.players.len().min(2)
+    }
+
+    /// Returns the winning player if the game is over
+    pub fn winner(&self) -> Option<usize> {
+        if self.game_over() {
+            self.players.iter().position(|player| !player.bankrupt)
+        } else {
+            None
+        }
+    }
+
+    /// Collect salary for passing go a number of times on the way to a space. Salary is doubled for landing on go
+    /// if the rules allow
+    pub(super) fn pass_go(&mut self, elem: usize, laps: usize) {
+        for lap in 1..=laps {
+            self.emit(Event::PassedGo { player: self.current });
+
+            if self.economy.is_some() {
+                let salary = if lap == laps && self.rules.double_go_salary && self.boarddef[elem] == Space::Go {
+                    GO_SALARY * 2
+                } else {
+                    GO_SALARY
+                };
+
+                self.player_mut().cash += salary;
+            }
+        }
+    }
+
+    /// Pay the fine to leave jail
+    pub(super) fn pay_jail_fine(&mut self) {
+        self.pay_fine(JAIL_FINE);
+    }
+
+    /// Pay a tax or fine to the bank, or in to the free parking pot if the rules allow
+    fn pay_fine(&mut self, amount: u32) {
+        let paid = self.pay(amount, None);
+
+        if self.rules.free_parking_pot
+            && let Some(economy) = &mut self.economy
+        {
+            economy.pot += paid;
+        }
+    }
+
+    /// Buy the space, pay rent or pay tax after landing on a space
+    pub(super) fn land(&mut self, elem: usize) {
+        // Card which moved the player here, if any
+        let card = self.card_move.take();
+
+        let Some(economy) = &self.economy else {
+            return;
+        };
+
+        let space = self.boarddef[elem];
+
+        if space == Space::FreeParking && self.rules.free_parking_pot {
+            // Collect the free parking pot
+            let pot = std::mem::take(&mut self.economy.as_mut().unwrap().pot);
+
+            self.player_mut().cash += pot;
+        } else if let Some(tax) = space.tax() {
+            // Pay tax to the bank
+            self.pay_fine(tax);
+        } else if let Some(price) = space.price() {
+            match economy.owner[elem] {
+                None => {
+                    // Buy the space if there's enough cash and the strategy wants to, otherwise auction it
+                    let player = self.player();
+
+                    if player.cash >= price && self.view().is_some_and(|view| player.strategy.buy(&view, elem)) {
+                        self.buy(elem, self.current, price);
+
+                        self.emit(Event::Bought {
+                            player: self.current,
+                            space: elem,
+                            price,
+                        });
+                    } else {
+                        self.auction(elem);
+                    }
+                }
+                Some(owner) if owner != self.current => {
+                    // Pay rent to the owner
+                    let rent = match card {
+                        Some(card @ Card::GoNext(_)) => {
+                            // The dice are thrown again (without the speed die) for utility rent
+                            let dice = match space.rent() {
+                                Some(Rent::Utility(_)) => self.dice.roll(&mut self.rng, true).dice_total(),
+                                _ => self.dice_total,
+                            };
+
+                            self.economy
+                                .as_ref()
+                                .map_or(0, |economy| economy.card_rent(&self.boarddef, elem, card, dice))
+                        }
+                        _ => economy.rent(&self.boarddef, elem, self.dice_total),
+                    };
+
+                    self.pay(rent, Some(owner));
+                }
+                Some(_) => (),
+            }
+        }
+    }
+
+    /// Transfer a space from the bank to a player for a price
+    fn buy(&mut self, elem: usize, player: usize, price: u32) {
+        self.players[player].cash -= price;
+
+        if let Some(economy) = &mut self.economy {
+            economy.owner[elem] = Some(player);
+        }
+    }
+
+    /// Auction a space to the highest bidder. Bidding starts with the current player and goes round the players
+    /// still in the game, each bid having to beat the highest so far, until all but one have dropped out
+    fn auction(&mut self, elem: usize) {
+        let mut bidders = (0..self.players.len())
+            .map(|i| (self.current + i) % self.players.len())
+            .filter(|&player| !self.players[player].bankrupt)
+            .collect::<Vec<_>>();
+
+        let mut highest = 0;
+        let mut leader = None;
+
+        loop {
+            let mut bid_made = false;
+
+            for bidder in bidders.clone() {
+                if leader == Some(bidder) {
+                    continue;
+                }
+
+                // Ask the strategy for a bid
+                let bid = self
+                    .view_for(bidder)
+                    .and_then(|view| self.players[bidder].strategy.bid(&view, elem, highest));
+
+                match bid {
+                    Some(amount) if amount > highest && amount <= self.players[bidder].cash => {
+                        highest = amount;
+                        leader = Some(bidder);
+                        bid_made = true;
+
+                        self.emit(Event::Bid {
+                            player: bidder,
+                            space: elem,
+                            amount,
+                        });
+                    }
+                    _ => {
+                        // Drop out of the auction
+                        bidders.retain(|&player| player != bidder);
+                    }
+                }
+            }
+
+            if !bid_made {
+                break;
+            }
+        }
+
+        if let Some(winner) = leader {
+            self.buy(elem, winner, highest);
+        }
+
+        self.emit(Event::Auctioned {
+            space: elem,
+            winner: leader,
+            price: highest,
+        });
+    }
+
+    /// Propose a trade from the current player to another player. The trade is made if it is valid and the other
+    /// player's strategy accepts it. Mortgaged spaces stay mortgaged, the receiver paying the bank 10% interest on
+    /// them. Returns true if the trade was made
+    pub fn propose_trade(&mut self, trade: Trade) -> bool {
+        if !self.valid_trade(&trade) {
+            return false;
+        }
+
+        let partner = trade.partner;
+
+        let accepted = self
+            .view_for(partner)
+            .is_some_and(|view| self.players[partner].strategy.accept_trade(&view, self.current, &trade));
+
+        if accepted {
+            let current = self.current;
+
+            if let Some(economy) = &mut self.economy {
+                self.players[current].cash -= economy.transfer_interests(&self.boarddef, &trade.take);
+                self.players[partner].cash -= economy.transfer_interests(&self.boarddef, &trade.give);
+
+                for &elem in &trade.give {
+                    economy.owner[elem] = Some(partner);
+                }
+
+                for &elem in &trade.take {
+                    economy.owner[elem] = Some(current);
+                }
+            }
+
+            self.players[current].cash = self.players[current].cash - trade.give_cash + trade.take_cash;
+            self.players[partner].cash = self.players[partner].cash - trade.take_cash + trade.give_cash;
+        }
+
+        self.emit(Event::Traded {
+            player: self.current,
+            trade,
+            accepted,
+        });
+
+        accepted
+    }
+
+    /// Returns true if the current player can propose a trade. Both players must be in the game, own the spaces
+    /// they give up and have the cash they pay, including the interest on mortgaged spaces they receive
+    fn valid_trade(&self, trade: &Trade) -> bool {
+        let Some(economy) = &self.economy else {
+            return false;
+        };
+
+        let partner = trade.partner;
+
+        if partner == self.current
+            || partner >= self.players.len()
+            || self.players[partner].bankrupt
+            || self.player().bankrupt
+        {
+            return false;
+        }
+
+        let mut spaces = trade.give.iter().chain(&trade.take).collect::<Vec<_>>();
+
+        spaces.sort();
+        spaces.dedup();
+
+        !spaces.is_empty()
+            && spaces.len() == trade.give.len() + trade.take.len()
+            && spaces.iter().all(|&&elem| elem < self.boarddef.len())
+            && trade
+                .give
+                .iter()
+                .all(|&elem| economy.can_trade(&self.boarddef, self.current, elem))
+            && trade
+                .take
+                .iter()
+                .all(|&elem| economy.can_trade(&self.boarddef, partner, elem))
+            && self.player().cash >= trade.give_cash + economy.transfer_interests(&self.boarddef, &trade.take)
+            && self.players[partner].cash >= trade.take_cash + economy.transfer_interests(&self.boarddef, &trade.give)
+    }
+
+    /// Let the current player propose a trade if their strategy wants to
+    pub(super) fn trade(&mut self) {
+        if self.player().bankrupt {
+            return;
+        }
+
+        let trade = self.view().and_then(|view| self.player().strategy.propose_trade(&view));
+
+        if let Some(trade) = trade {
+            self.propose_trade(trade);
+        }
+    }
+
+    /// Pay an amount to another player or to the bank, selling buildings and mortgaging spaces if short of cash.
+    /// The player is bankrupt if they can't pay. Returns the amount paid
+    pub(super) fn pay(&mut self, amount: u32, creditor: Option<usize>) -> u32 {
+        if self.economy.is_none() {
+            return 0;
+        }
+
+        // Raise cash if needed
+        if self.player().cash < amount {
+            self.sell_buildings(amount);
+            self.mortgage_spaces(amount);
+        }
+
+        // Pay as much as possible
+        let paid = amount.min(self.player().cash);
+
+        self.player_mut().cash -= paid;
+
+        if let Some(creditor) = creditor {
+            self.players[creditor].cash += paid;
+        }
+
+        if paid < amount {
+            self.bankrupt(creditor);
+        }
+
+        paid
+    }
+
+    /// Mortgage spaces while the strategy chooses to until the player has an amount of cash
+    fn mortgage_spaces(&mut self, amount: u32) {
+        loop {
+            let Some(view) = self.view() else {
+                return;
+            };
+
+            let player = self.player();
+
+            if player.cash >= amount {
+                return;
+            }
+
+            // Find spaces which can be mortgaged
+            let candidates = (0..self.boarddef.len())
+                .filter(|&elem| view.economy.can_mortgage(&self.boarddef, self.current, elem))
+                .collect::<Vec<_>>();
+
+            // Ask the strategy which to mortgage
+            let Some(elem) = player
+                .strategy
+                .mortgage(&view, &candidates, amount - player.cash)
+                .filter(|elem| candidates.contains(elem))
+            else {
+                return;
+            };
+
+            self.mortgage(elem);
+        }
+    }
+
+    /// Mortgage a space for half its price
+    pub(super) fn mortgage(&mut self, elem: usize) {
+        let Some(economy) = &mut self.economy else {
+            return;
+        };
+
+        economy.mortgaged[elem] = true;
+
+        let value = economy.mortgage_value(&self.boarddef, elem);
+
+        self.player_mut().cash += value;
+
+        self.emit(Event::Mortgaged {
+            player: self.current,
+            space: elem,
+        });
+    }
+
+    /// Pay off mortgages while the strategy chooses to
+    pub(super) fn unmortgage_spaces(&mut self) {
+        loop {
+            let Some(view) = self.view() else {
+                return;
+            };
+
+            let player = self.player();
+
+            if player.bankrupt {
+                return;
+            }
+
+            // Find mortgaged spaces which can be afforded
+            let candidates = (0..self.boarddef.len())
+                .filter(|&elem| {
+                    view.economy.can_unmortgage(self.current, elem)
+                        && view.economy.unmortgage_cost(&self.boarddef, elem) <= player.cash
+                })
+                .collect::<Vec<_>>();
+
+            // Ask the strategy which to pay off
+            let Some(elem) = player
+                .strategy
+                .unmortgage(&view, &candidates)
+                .filter(|elem| candidates.contains(elem))
+            else {
+                return;
+            };
+
+            let cost = view.economy.unmortgage_cost(&self.boarddef, elem);
+
+            self.player_mut().cash -= cost;
+
+            if let Some(economy) = &mut self.economy {
+                economy.mortgaged[elem] = false;
+            }
+
+            self.emit(Event::Unmortgaged {
+                player: self.current,
+                space: elem,
+            });
+        }
+    }
+
+    /// Sell buildings back to the bank while the strategy chooses to until the player has an amount of cash
+    fn sell_buildings(&mut self, amount: u32) {
+        loop {
+            let Some(view) = self.view() else {
+                return;
+            };
+
+            let player = self.player();
+
+            if player.cash >= amount {
+                return;
+            }
+
+            // Find spaces with buildings which can be sold
+            let candidates = (0..self.boarddef.len())
+                .filter(|&elem| view.economy.can_sell(&self.boarddef, self.current, elem))
+                .collect::<Vec<_>>();
+
+            // Ask the strategy which to sell
+            let Some(elem) = player
+                .strategy
+                .sell(&view, &candidates, amount - player.cash)
+                .filter(|elem| candidates.contains(elem))
+            else {
+                return;
+            };
+
+            self.sell_building(elem);
+        }
+    }
+
+    /// Sell a building on a space back to the bank for half the price paid. A hotel is sold for four houses if the
+    /// bank has them, otherwise the missing houses are sold as well
+    pub(super) fn sell_building(&mut self, elem: usize) {
+        let Some(economy) = &mut self.economy else {
+            return;
+        };
+
+        let buildings = economy.buildings_after_sale(elem);
+        let sold = economy.buildings[elem] - buildings;
+
+        economy.buildings[elem] = buildings;
+
+        let cost = self.boarddef[elem].house_cost().unwrap_or(0);
+
+        self.player_mut().cash += (cost / 2) * sold as u32;
+
+        self.emit(Event::BuildingSold {
+            player: self.current,
+            space: elem,
+            buildings,
+        });
+    }
+
+    /// Remove the current player from the game. Any buildings left are sold to the bank. When bankrupted by
+    /// another player the creditor gets the proceeds, the spaces (still mortgaged, paying the bank 10% interest on
+    /// them) and get out of jail free cards, otherwise the cards go back to the decks and the spaces are returned to
+    /// the bank and auctioned
+    pub(super) fn bankrupt(&mut self, creditor: Option<usize>) {
+        let current = self.current;
+
+        self.player_mut().bankrupt = true;
+
+        self.emit(Event::Bankrupt {
+            player: current,
+            creditor,
+        });
+
+        let Some(economy) = &mut self.economy else {
+            return;
+        };
+
+        // Sell the remaining buildings
+        let mut proceeds = 0;
+
+        for elem in economy.owned_by(current).collect::<Vec<_>>() {
+            let cost = self.boarddef[elem].house_cost().unwrap_or(0);
+
+            proceeds += (cost / 2) * economy.buildings[elem] as u32;
+            economy.buildings[elem] = 0;
+        }
+
+        let spaces = economy.owned_by(current).collect::<Vec<_>>();
+        let jailcards = std::mem::take(&mut self.player_mut().jailcards);
+
+        proceeds += std::mem::take(&mut self.player_mut().cash);
+
+        match creditor {
+            Some(creditor) => {
+                // Transfer everything to the creditor
+                let mut interest = 0;
+
+                if let Some(economy) = &mut self.economy {
+                    interest = economy.transfer_interests(&self.boarddef, &spaces);
+
+                    for &elem in &spaces {
+                        economy.owner[elem] = Some(creditor);
+                    }
+                }
+
+                // The creditor pays the interest on the mortgaged spaces as far as their cash allows
+                self.players[creditor].cash = (self.players[creditor].cash + proceeds).saturating_sub(interest);
+                self.players[creditor].jailcards.extend(jailcards);
+            }
+            None => {
+                // Return get out of jail free cards to the decks
+                for deck in jailcards {
+                    self.deck_mut(deck).push_back(Card::GetOutOfJail);
+                }
+
+                // Return the spaces to the bank and auction them
+                for elem in spaces {
+                    if let Some(economy) = &mut self.economy {
+                        economy.owner[elem] = None;
+                        economy.mortgaged[elem] = false;
+                    }
+
+                    self.auction(elem);
+                }
+            }
+        }
+
+        if let Some(winner) = self.winner() {
+            self.emit(Event::GameOver {
+                winner,
+                turns: self.stats.turns(),
+            });
+        }
+    }
+
+    /// Play turns until the game is over or a number of turns have been taken, returning the winner if there is one
+    pub fn play_game(&mut self, max_turns: u64) -> Option<usize> {
+        while !self.game_over() && self.stats.turns() < max_turns {
+            self.turn();
+        }
+
+        self.winner()
+    }
+
+    /// Build houses evenly on complete sets while the strategy chooses to
+    pub(super) fn build(&mut self) {
+        loop {
+            let Some(view) = self.view() else {
+                return;
+            };
+
+            let player = self.player();
+
+            if player.bankrupt {
+                return;
+            }
+
+            // Find spaces which can be built on and afforded
+            let candidates = (0..self.boarddef.len())
+                .filter(|&elem| {
+                    view.economy.can_build(&self.boarddef, self.current, elem)
+                        && self.boarddef[elem].house_cost().is_some_and(|cost| cost <= player.cash)
+                })
+                .collect::<Vec<_>>();
+
+            // Ask the strategy which to build on
+            let Some(elem) = player
+                .strategy
+                .build(&view, &candidates)
+                .filter(|elem| candidates.contains(elem))
+            else {
+                return;
+            };
+
+            let cost = self.boarddef[elem].house_cost().unwrap();
+
+            self.player_mut().cash -= cost;
+
+            let Some(economy) = &mut self.economy else {
+                return;
+            };
+
+            economy.buildings[elem] += 1;
+
+            let buildings = economy.buildings[elem];
+
+            self.emit(Event::BuildingBought {
+                player: self.current,
+                space: elem,
+                buildings,
+            });
+        }
+    }
+
+    /// Returns the view of the game for the current player if playing for money
+    fn view(&self) -> Option<GameView<'_>> {
+        self.view_for(self.current)
+    }
+
+    /// Returns the view of the game for a player if playing for money
+    fn view_for(&self, player: usize) -> Option<GameView<'_>> {
+        self.economy.as_ref().map(|economy| GameView {
+            boarddef: &self.boarddef,
+            economy,
+            players: &self.players,
+            player,
+        })
+    }
+}
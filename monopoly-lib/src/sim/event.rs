@@ -0,0 +1,127 @@
+use std::{fmt::Debug, io};
+
+use serde::Serialize;
+
+use crate::card::{Card, Deck};
+use crate::dice::Roll;
+use crate::movereason::MoveReason;
+use crate::strategy::Trade;
+
+/// Something which happened during a turn
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event")]
+pub enum Event {
+    /// A player started a turn
+    TurnStarted { player: usize, turn: u64 },
+    /// A player rolled the dice
+    DiceRolled { player: usize, roll: Roll },
+    /// A player moved to a space (including spaces which jump straight on elsewhere)
+    Moved {
+        player: usize,
+        from: usize,
+        to: usize,
+        reason: MoveReason,
+    },
+    /// A player passed go
+    PassedGo { player: usize },
+    /// A player drew a card
+    CardDrawn { player: usize, deck: Deck, card: Card },
+    /// A player was sent to jail
+    SentToJail { player: usize, reason: MoveReason },
+    /// A player in jail failed to roll a double
+    StayedInJail { player: usize, attempt: u8 },
+    /// A player left jail
+    ReleasedFromJail { player: usize, release: Release },
+    /// A player bought a space from the bank at its price
+    Bought { player: usize, space: usize, price: u32 },
+    /// A player bid for a space at auction
+    Bid { player: usize, space: usize, amount: u32 },
+    /// An auction finished, selling a space to the winner (if there were any bids) at a price
+    Auctioned {
+        space: usize,
+        winner: Option<usize>,
+        price: u32,
+    },
+    /// A player proposed a trade to another player which was accepted or declined
+    Traded {
+        player: usize,
+        trade: Trade,
+        accepted: bool,
+    },
+    /// A player built a house or hotel on a space, leaving a number of buildings (5 is a hotel)
+    BuildingBought { player: usize, space: usize, buildings: u8 },
+    /// A player sold a house or hotel on a space back to the bank, leaving a number of buildings
+    BuildingSold { player: usize, space: usize, buildings: u8 },
+    /// A player mortgaged a space
+    Mortgaged { player: usize, space: usize },
+    /// A player paid off the mortgage on a space
+    Unmortgaged { player: usize, space: usize },
+    /// A player couldn't pay a debt to another player (or the bank if no creditor) and is out of the game
+    Bankrupt { player: usize, creditor: Option<usize> },
+    /// The game finished after a number of turns with one player left
+    GameOver { winner: usize, turns: u64 },
+    /// A player finished a turn having rolled a number of doubles
+    TurnEnded { player: usize, doubles: usize },
+}
+
+/// How a player left jail
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Release {
+    /// Used a get out of jail free card
+    Card,
+    /// Paid the fine
+    Fine,
+    /// Rolled a double
+    Double,
+    /// Paid the fine after failing to roll a double on every attempt
+    Attempts,
+}
+
+/// Receives events as the simulation runs
+pub trait Observer: Debug + Send {
+    /// Called for each event
+    fn event(&mut self, event: &Event);
+
+    /// Called when the observer is removed from the board, returning any error which occurred
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes events to a JSON Lines stream, one JSON object per line
+#[derive(Debug)]
+pub struct JsonLinesWriter<W: io::Write + Debug + Send> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write + Debug + Send> JsonLinesWriter<W> {
+    /// Create a new JSON Lines writer
+    pub fn new(writer: W) -> Self {
+        Self { writer, error: None }
+    }
+}
+
+impl<W: io::Write + Debug + Send> Observer for JsonLinesWriter<W> {
+    fn event(&mut self, event: &Event) {
+        // Stop writing after the first error
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = serde_json::to_writer(&mut self.writer, event)
+            .map_err(io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+
+        if let Err(e) = result {
+            self.error = Some(e);
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => self.writer.flush(),
+        }
+    }
+}
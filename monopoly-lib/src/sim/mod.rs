@@ -1,113 +1,281 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, io, sync::Arc};
 
-use crate::chance::CHCard;
-use crate::commchest::CCCard;
+use crate::boarddef::BoardDefinition;
+use crate::card::{Card, CardMove, Deck};
+use crate::dice::{DiceModel, Roll};
 use crate::movereason::MoveReason;
-use crate::space::{SPACES, Space};
-use crate::strategy::Strategy;
+use crate::rules::Rules;
+use crate::space::Space;
+use crate::strategy::{JailAction, PayJail, PlayerStrategy};
 use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
 use strum::EnumCount;
 
-type CardChoose<T> = fn(rng: &mut ThreadRng, &mut VecDeque<T>) -> T;
-type DiceRollCb = fn(board: &mut Board, doubles: usize) -> (u8, u8);
+pub mod batch;
+pub mod economy;
+pub mod event;
+pub mod player;
+pub mod runner;
+pub mod snapshot;
+pub mod stats;
+pub mod tournament;
+
+use batch::ConfidenceInterval;
+use economy::Economy;
+use event::{Event, Observer, Release};
+use player::Player;
+use stats::Stats;
+
+/// Default random number generator used by the simulation. ChaCha8 is used as it produces the same
+/// sequence for a given seed on every platform and rand release
+pub type SimRng = ChaCha8Rng;
+
+type CardChoose<T, R> = fn(rng: &mut R, &mut VecDeque<T>) -> T;
+#[cfg(test)]
+type DiceRollCb<R> = fn(board: &mut Board<R>, doubles: usize) -> (u8, u8);
 
 #[derive(Debug)]
-pub struct Board {
-    strategy: Strategy,
-    position: usize,
-    jailroll: u8,
-    ccdeck: VecDeque<CCCard>,
-    cccardchoose: CardChoose<CCCard>,
-    chdeck: VecDeque<CHCard>,
-    chcardchoose: CardChoose<CHCard>,
-    arrivals: [u64; SPACES.len()],
-    arrival_reason: [[u64; MoveReason::COUNT]; SPACES.len()],
-    moves: u64,
-    turns: u64,
-    doubles: [u64; 3],
-    rollfreq: [u64; 11],
-    rng: ThreadRng,
+pub struct Board<R = SimRng> {
+    boarddef: BoardDefinition,
+    players: Vec<Player>,
+    current: usize,
+    ccdeck: VecDeque<Card>,
+    cccardchoose: CardChoose<Card, R>,
+    chdeck: VecDeque<Card>,
+    chcardchoose: CardChoose<Card, R>,
+    randomcard: bool,
+    dice: DiceModel,
+    rules: Rules,
+    stats: Stats,
+    economy: Option<Economy>,
+    dice_total: u8,
+    card_move: Option<Card>,
+    seed: u64,
+    rng: R,
+    observer: Option<Box<dyn Observer>>,
+    events: Option<Vec<Event>>,
 }
 
 impl Default for Board {
     fn default() -> Self {
-        // Default pay to get out of jail, cycle card decks when choosing
-        Self::new(Strategy::PayJail, false)
+        // Default classic board, one player, pay to get out of jail, cycle card decks when choosing
+        Self::new(BoardDefinition::default(), vec![Arc::new(PayJail)], false)
     }
 }
 
 impl Board {
-    /// Create a new board with a given strategy and car selection method
-    pub fn new(strategy: Strategy, randomcard: bool) -> Self {
+    /// Create a new board with a given layout, player strategies (one per player) and card selection method
+    /// using a random seed
+    pub fn new(boarddef: BoardDefinition, strategies: Vec<Arc<dyn PlayerStrategy>>, randomcard: bool) -> Self {
+        Self::with_seed(boarddef, strategies, randomcard, random())
+    }
+}
+
+impl<R: RngCore + SeedableRng> Board<R> {
+    /// Create a new board with a given layout, player strategies (one per player) and card selection method.
+    /// Boards created with the same seed produce identical results
+    pub fn with_seed(
+        boarddef: BoardDefinition,
+        strategies: Vec<Arc<dyn PlayerStrategy>>,
+        randomcard: bool,
+        seed: u64,
+    ) -> Self {
+        assert!(!strategies.is_empty(), "At least one player is required");
+
         // Create random number generator
-        let mut rng = thread_rng();
+        let mut rng = R::seed_from_u64(seed);
 
         // Set up community chest card deck
-        let mut ccdeck = CCCard::build_deck();
+        let mut ccdeck = boarddef.community_chest().build_deck();
         Self::shuffle_deck(&mut rng, &mut ccdeck);
 
         // Set up chance card deck
-        let mut chdeck = CHCard::build_deck();
+        let mut chdeck = boarddef.chance().build_deck();
         Self::shuffle_deck(&mut rng, &mut chdeck);
 
+        // Create players (all starting on go)
+        let players = strategies
+            .into_iter()
+            .map(|strategy| Player::new(boarddef.len(), strategy))
+            .collect();
+
+        // Create aggregate statistics
+        let stats = Stats::new(boarddef.len());
+
         Self {
-            strategy,
-            position: 0,
-            jailroll: 0,
+            boarddef,
+            players,
+            current: 0,
             ccdeck,
             cccardchoose: if randomcard { Self::random_card } else { Self::next_card },
             chdeck,
             chcardchoose: if randomcard { Self::random_card } else { Self::next_card },
-            arrivals: [0; SPACES.len()],
-            arrival_reason: [[0; MoveReason::COUNT]; SPACES.len()],
-            moves: 0,
-            turns: 0,
-            doubles: [0; 3],
-            rollfreq: [0; 11],
+            randomcard,
+            dice: DiceModel::classic(),
+            rules: Rules::classic(),
+            stats,
+            economy: None,
+            dice_total: 0,
+            card_move: None,
+            seed,
             rng,
+            observer: None,
+            events: None,
         }
     }
 
-    // Take a turn (may involve several moves when rolling double)
+    // Take a turn for the current player (may involve several moves when rolling double)
     pub fn turn(&mut self) {
-        self.turn_with_dice(|board, _| board.roll_dice());
+        self.turn_with_roll(|board, _| board.roll_dice());
+    }
+
+    /// Use a different dice model for the rest of the simulation
+    pub fn set_dice(&mut self, dice: DiceModel) {
+        self.dice = dice;
+    }
+
+    /// Returns the dice model in use
+    pub fn dice(&self) -> &DiceModel {
+        &self.dice
+    }
+
+    /// Use different rule variants for the rest of the simulation
+    pub fn set_rules(&mut self, rules: Rules) {
+        rules.validate().expect("Invalid rules");
+
+        self.rules = rules;
+    }
+
+    /// Returns the rule variants in use
+    pub fn rules(&self) -> &Rules {
+        &self.rules
     }
 
-    /// Take a turn with a callback to get dice rolls
-    fn turn_with_dice(&mut self, dice: DiceRollCb) {
+    /// Take a turn for the current player returning the events which happened
+    pub fn turn_events(&mut self) -> Vec<Event> {
+        self.events = Some(Vec::new());
+        self.turn();
+        self.events.take().unwrap_or_default()
+    }
+
+    /// Send events to an observer as the simulation runs, replacing any existing observer
+    pub fn set_observer(&mut self, observer: Box<dyn Observer>) -> io::Result<()> {
+        let result = self.remove_observer();
+
+        self.observer = Some(observer);
+
+        result
+    }
+
+    /// Remove the observer, returning any error it encountered
+    pub fn remove_observer(&mut self) -> io::Result<()> {
+        match self.observer.take() {
+            Some(mut observer) => observer.finish(),
+            None => Ok(()),
+        }
+    }
+
+    /// Take a turn for the current player with a callback to get the values of two dice
+    #[cfg(test)]
+    fn turn_with_dice(&mut self, dice: DiceRollCb<R>) {
+        self.turn_with_roll(|board, doubles| dice(board, doubles).into());
+    }
+
+    /// Take a turn for the current player with a callback to get dice rolls, then pass to the next player
+    fn turn_with_roll<F>(&mut self, mut dice: F)
+    where
+        F: FnMut(&mut Self, usize) -> Roll,
+    {
+        // Nothing to do if the game has finished
+        if self.game_over() {
+            return;
+        }
+
+        self.emit(Event::TurnStarted {
+            player: self.current,
+            turn: self.stats.turns() + 1,
+        });
+
+        // Ask the strategy how to leave jail
+        if self.player().jailroll > 0 {
+            let has_card = !self.player().jailcards.is_empty();
+
+            let action = match self.player().strategy.leave_jail(self.player().jailroll, has_card) {
+                JailAction::Mixed(prob) => {
+                    if self.rng.gen_bool(prob.as_f64()) {
+                        JailAction::Roll
+                    } else {
+                        JailAction::UseCard
+                    }
+                }
+                action => action,
+            };
+
+            match action {
+                JailAction::Roll => (),
+                JailAction::UseCard if has_card => {
+                    // Use a get out of jail free card and roll as normal
+                    self.player_mut().jailroll = 0;
+                    self.use_jail_card();
+                    self.emit_release(Release::Card);
+                }
+                _ => {
+                    // Pay the fine and roll as normal
+                    self.player_mut().jailroll = 0;
+                    self.pay_jail_fine();
+                    self.emit_release(Release::Fine);
+                }
+            }
+        }
+
         // Increment turns
-        self.turns += 1;
+        self.stats.record_turn();
+        self.players[self.current].stats.record_turn();
 
         // Keep track of doubles
         let mut doubles = 0;
 
-        loop {
+        while !self.player().bankrupt {
             // Roll the dice
-            let (d1, d2) = dice(self, doubles);
+            let roll = dice(self, doubles);
+
+            self.emit(Event::DiceRolled {
+                player: self.current,
+                roll,
+            });
 
             // Calculate total
-            let total = d1 + d2;
+            let total = roll.total();
+            self.dice_total = total;
 
             // Count rolled sum
-            self.rollfreq[total as usize - 2] += 1;
+            self.stats.record_roll(total);
+            self.players[self.current].stats.record_roll(total);
 
             // Thrown a double?
-            let double = d1 == d2;
+            let double = roll.double();
 
-            if self.jailroll > 0 && self.strategy == Strategy::JailWait {
+            if self.player().jailroll > 0 {
                 // In jail, rolling to exit
                 if double {
                     // Rolled a double - player moves but does not get another go
-                    self.move_to((self.position + total as usize) % SPACES.len(), MoveReason::Roll);
+                    self.emit_release(Release::Double);
+                    self.roll_move(&roll);
                 } else {
                     // Not rolled a double
-                    self.jailroll += 1;
+                    self.player_mut().jailroll += 1;
 
-                    if self.jailroll == 4 {
-                        // Not rolled a double in 3 goes - move to just visiting
-                        self.jailroll = 0;
+                    if self.player().jailroll > self.rules.jail_attempts {
+                        // Not rolled a double on any attempt - pay the fine and move to just visiting
+                        self.player_mut().jailroll = 0;
+                        self.emit_release(Release::Attempts);
                         self.update_arrivals(MoveReason::ExitJail);
+                        self.pay_jail_fine();
                     } else {
+                        self.emit(Event::StayedInJail {
+                            player: self.current,
+                            attempt: self.player().jailroll - 1,
+                        });
                         self.update_arrivals(MoveReason::NoDouble);
                     }
                 }
@@ -120,137 +288,234 @@ impl Board {
                 // Count doubles
                 doubles += 1;
 
-                if doubles == 3 {
+                if doubles == 3 && self.rules.triple_double_jail {
                     // 3 doubles in a row - go to jail
-                    self.move_to(Space::find(Space::Visit), MoveReason::TripleDouble);
+                    self.move_to(self.boarddef.find(Space::Visit), MoveReason::TripleDouble);
                     break;
                 }
             }
 
             // Make the move
-            self.move_to((self.position + total as usize) % SPACES.len(), MoveReason::Roll);
+            self.roll_move(&roll);
 
-            // If not rolled a double or in jail then go is over
-            if !double || self.jailroll > 0 {
+            // If not rolled a double, rolled the third double, in jail or bankrupt then go is over
+            if !double || doubles == 3 || self.player().jailroll > 0 || self.player().bankrupt {
                 break;
             }
         }
 
         // Count doubles (not cumulative)
-        if doubles > 0 {
-            self.doubles[doubles - 1] += 1;
+        self.stats.record_doubles(doubles);
+        self.players[self.current].stats.record_doubles(doubles);
+
+        self.emit(Event::TurnEnded {
+            player: self.current,
+            doubles,
+        });
+
+        // Trade, pay off mortgages and build houses if playing for money
+        self.trade();
+        self.unmortgage_spaces();
+        self.build();
+
+        // Pass to the next player still in the game
+        for _ in 0..self.players.len() {
+            self.current = (self.current + 1) % self.players.len();
+
+            if !self.player().bankrupt {
+                break;
+            }
         }
     }
 
-    /// Returns the strategy in use
-    pub fn strategy(&self) -> Strategy {
-        self.strategy
+    /// Returns the board layout in use
+    pub fn boarddef(&self) -> &BoardDefinition {
+        &self.boarddef
+    }
+
+    /// Returns the random number generator seed
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the players on the board
+    pub fn players(&self) -> &[Player] {
+        &self.players
     }
 
-    /// Returns reference to the space arrival count array
+    /// Returns the index of the player whose turn is next
+    pub fn current_player(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the statistics aggregated over all players
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// Returns reference to the space arrival count array (all players)
     pub fn arrivals(&self) -> &[u64] {
-        &self.arrivals
+        self.stats.arrivals()
     }
 
-    /// Returns number of arrivals for a given space
+    /// Returns number of arrivals for a given space (all players)
     pub fn arrivals_on(&self, elem: usize) -> u64 {
-        self.arrivals[elem]
+        self.stats.arrivals()[elem]
     }
 
-    /// Returns reference to the space arrival reason array
-    pub fn arrival_reasons(&self) -> &[[u64; MoveReason::COUNT]; SPACES.len()] {
-        &self.arrival_reason
+    /// Returns the arrival frequency for a given space with a confidence interval at a confidence level (eg. 0.95),
+    /// allowing for successive moves not being independent (all players)
+    pub fn arrival_interval(&self, elem: usize, confidence: f64) -> Option<ConfidenceInterval> {
+        self.stats.arrival_interval(&[elem], confidence)
     }
 
-    /// Returns arrival reasons for a given space
+    /// Returns reference to the space arrival reason array (all players)
+    pub fn arrival_reasons(&self) -> &[[u64; MoveReason::COUNT]] {
+        self.stats.arrival_reasons()
+    }
+
+    /// Returns arrival reasons for a given space (all players)
     pub fn arrival_reasons_on(&self, elem: usize) -> &[u64] {
-        &self.arrival_reason[elem]
+        &self.stats.arrival_reasons()[elem]
     }
 
-    /// Returns the number of moves made
+    /// Returns the number of moves made (all players)
     pub fn moves(&self) -> u64 {
-        self.moves
+        self.stats.moves()
     }
 
-    /// Returns the number of turns taken (can involve >1 move)
+    /// Returns the number of turns taken (can involve >1 move, all players)
     pub fn turns(&self) -> u64 {
-        self.turns
+        self.stats.turns()
     }
 
-    /// Returns a reference to the doubles count array
+    /// Returns a reference to the doubles count array (all players)
     pub fn doubles(&self) -> &[u64] {
-        &self.doubles
+        self.stats.doubles()
     }
 
-    /// Returns the number of turns with a given number of doubles thrown
+    /// Returns the number of turns with a given number of doubles thrown (all players)
     pub fn doubles_elem(&self, elem: usize) -> u64 {
-        self.doubles[elem]
+        self.stats.doubles()[elem]
     }
 
-    /// Returns a reference to the roll sum frequencies
+    /// Returns a reference to the roll sum frequencies (all players)
     pub fn rollfreq(&self) -> &[u64] {
-        &self.rollfreq
+        self.stats.rollfreq()
+    }
+
+    /// Returns the player whose turn it is
+    fn player(&self) -> &Player {
+        &self.players[self.current]
+    }
+
+    /// Returns the player whose turn it is (mutable)
+    fn player_mut(&mut self) -> &mut Player {
+        &mut self.players[self.current]
+    }
+
+    /// Move forward by a dice roll, collecting salary each time go is passed
+    fn roll_move(&mut self, roll: &Roll) {
+        let position = self.player().position;
+        let elem = self.dice.destination(&self.boarddef, position, roll);
+
+        // Count the laps of the board made by the dice, which can be more than one on a short board, plus any wrap
+        // past go when advancing with the speed die
+        let spaces = self.boarddef.len();
+        let moved = position + roll.distance() as usize;
+        let laps = moved / spaces + (elem < moved % spaces) as usize;
+
+        self.pass_go(elem, laps);
+
+        self.move_to(elem, MoveReason::Roll);
     }
 
     /// Move to a given space with a move reason
     fn move_to(&mut self, elem: usize, reason: MoveReason) {
+        self.emit(Event::Moved {
+            player: self.current,
+            from: self.player().position,
+            to: elem,
+            reason,
+        });
+
         // Set current position
-        self.position = elem;
+        self.player_mut().position = elem;
 
         // Perform any actions necessary
-        match SPACES[self.position] {
-            Space::GoToJail => self.move_to(Space::find(Space::Visit), MoveReason::GoToJail),
+        match self.boarddef[elem] {
+            Space::GoToJail => self.move_to(self.boarddef.find(Space::Visit), MoveReason::GoToJail),
             Space::CommunityChest(_) => self.draw_community_chest(reason),
             Space::Chance(_) => self.draw_chance(reason),
             _ => (),
         }
 
         // Jumped to a different space?
-        if self.position == elem {
+        if self.player().position == elem {
             // No - update state and statistics
-            if SPACES[self.position] == Space::Visit && reason != MoveReason::Roll && reason != MoveReason::ExitJail {
+            if self.boarddef[elem] == Space::Visit && reason != MoveReason::Roll && reason != MoveReason::ExitJail {
                 // Going in to jail
-                self.jailroll = 1;
+                self.player_mut().jailroll = 1;
+
+                self.emit(Event::SentToJail {
+                    player: self.current,
+                    reason,
+                });
             } else {
                 // Not in jail
-                self.jailroll = 0;
+                self.player_mut().jailroll = 0;
             }
 
             // Update arrival counts and reasons
             self.update_arrivals(reason);
+
+            // Buy, pay rent or pay tax if playing for money
+            self.land(elem);
         }
     }
 
     /// Update statistics when arriving on a space
     fn update_arrivals(&mut self, reason: MoveReason) {
-        let recordelem = if SPACES[self.position] == Space::Visit {
+        let position = self.player().position;
+
+        let recordelem = if self.boarddef[position] == Space::Visit {
             match reason {
-                MoveReason::Roll | MoveReason::ExitJail => self.position,
+                MoveReason::Roll | MoveReason::ExitJail => position,
                 _ => {
                     // Reasons for entering jail are recorded on the 'Go to jail' space
-                    Space::find(Space::GoToJail)
+                    self.boarddef.find(Space::GoToJail)
                 }
             }
         } else {
-            self.position
+            position
         };
 
-        // Record arrival at this space
-        self.arrivals[recordelem] += 1;
-
-        // Record move
-        self.moves += 1;
+        // Record arrival for the player and in the aggregate statistics
+        self.stats.record_arrival(recordelem, reason);
+        self.player_mut().stats.record_arrival(recordelem, reason);
+    }
 
-        // Record move reason (all except Rolled)
-        let reason_elem = reason as isize;
+    /// Send an event to the observer and record it if collecting the events for a turn
+    fn emit(&mut self, event: Event) {
+        if let Some(observer) = &mut self.observer {
+            observer.event(&event);
+        }
 
-        if reason_elem >= 0 {
-            self.arrival_reason[recordelem][reason_elem as usize] += 1;
+        if let Some(events) = &mut self.events {
+            events.push(event);
         }
     }
 
+    /// Send a jail release event for the current player
+    fn emit_release(&mut self, release: Release) {
+        self.emit(Event::ReleasedFromJail {
+            player: self.current,
+            release,
+        });
+    }
+
     /// Shuffles a deck of cards
-    fn shuffle_deck<T: Copy>(rng: &mut ThreadRng, deck: &mut VecDeque<T>) {
+    fn shuffle_deck<T: Copy>(rng: &mut R, deck: &mut VecDeque<T>) {
         for _ in 0..(deck.len() * 4) {
             let elem = rng.gen_range(0..(deck.len()));
 
@@ -261,13 +526,13 @@ impl Board {
     }
 
     /// Choose a randon card
-    fn random_card<T: Copy>(rng: &mut ThreadRng, deck: &mut VecDeque<T>) -> T {
+    fn random_card<T: Copy>(rng: &mut R, deck: &mut VecDeque<T>) -> T {
         let elem = rng.gen_range(0..deck.len());
         deck[elem]
     }
 
     /// Choose the next card
-    fn next_card<T: Copy>(_rng: &mut ThreadRng, deck: &mut VecDeque<T>) -> T {
+    fn next_card<T: Copy>(_rng: &mut R, deck: &mut VecDeque<T>) -> T {
         let card = deck.pop_front().unwrap();
         deck.push_back(card);
 
@@ -278,48 +543,103 @@ impl Board {
     fn draw_chance(&mut self, reason: MoveReason) {
         let card = (self.chcardchoose)(&mut self.rng, &mut self.chdeck);
 
+        self.emit(Event::CardDrawn {
+            player: self.current,
+            deck: Deck::Chance,
+            card,
+        });
+
+        if card == Card::GetOutOfJail {
+            self.keep_jail_card(Deck::Chance);
+        }
+
         let reason = match reason {
-            MoveReason::Roll => MoveReason::CHCard,
+            MoveReason::Roll | MoveReason::CHCard | MoveReason::CCCard | MoveReason::CHCardCCCard => MoveReason::CHCard,
             _ => panic!("Unexpected reason in draw chance card"),
         };
 
-        match card {
-            CHCard::GoGo => self.move_to(Space::find(Space::Go), reason),
-            CHCard::GoJail => self.move_to(Space::find(Space::Visit), reason),
-            CHCard::GoProperty(set, n) => self.move_to(Space::find(Space::Property(set, n)), reason),
-            CHCard::GoRail(n) => self.move_to(Space::find(Space::Rail(n)), reason),
-            CHCard::GoNextRail => self.move_to(Space::next_rail(self.position), reason),
-            CHCard::GoNextUtil => self.move_to(Space::next_util(self.position), reason),
-            CHCard::Back3 => self.move_to(self.position - 3, reason),
-            _ => (),
-        }
+        self.action_card(card, reason);
     }
 
     /// Draw a community chest card and action it
     fn draw_community_chest(&mut self, reason: MoveReason) {
         let card = (self.cccardchoose)(&mut self.rng, &mut self.ccdeck);
 
+        self.emit(Event::CardDrawn {
+            player: self.current,
+            deck: Deck::CommunityChest,
+            card,
+        });
+
+        if card == Card::GetOutOfJail {
+            self.keep_jail_card(Deck::CommunityChest);
+        }
+
         let reason = match reason {
-            MoveReason::Roll => MoveReason::CCCard,
+            MoveReason::Roll | MoveReason::CCCard | MoveReason::CHCardCCCard => MoveReason::CCCard,
             MoveReason::CHCard => MoveReason::CHCardCCCard,
             _ => panic!("Unexpected reason in draw community chest card"),
         };
 
-        match card {
-            CCCard::GoGo => self.move_to(Space::find(Space::Go), reason),
-            CCCard::GoJail => self.move_to(Space::find(Space::Visit), reason),
-            _ => (),
+        self.action_card(card, reason);
+    }
+
+    /// Returns a card deck
+    fn deck_mut(&mut self, deck: Deck) -> &mut VecDeque<Card> {
+        match deck {
+            Deck::Chance => &mut self.chdeck,
+            Deck::CommunityChest => &mut self.ccdeck,
         }
     }
 
-    /// Roll both dice and return values
-    fn roll_dice(&mut self) -> (u8, u8) {
-        (self.die_roll(), self.die_roll())
+    /// Remove a drawn get out of jail free card from its deck and give it to the current player
+    fn keep_jail_card(&mut self, deck: Deck) {
+        let cards = self.deck_mut(deck);
+
+        if let Some(elem) = cards.iter().position(|card| *card == Card::GetOutOfJail) {
+            cards.remove(elem);
+        }
+
+        self.player_mut().jailcards.push(deck);
     }
 
-    /// Roll a single die
-    fn die_roll(&mut self) -> u8 {
-        self.rng.gen_range(1..=6)
+    /// Use one of the current player's get out of jail free cards (chance first), returning it to the bottom of
+    /// its deck
+    fn use_jail_card(&mut self) {
+        let jailcards = &mut self.player_mut().jailcards;
+
+        let elem = jailcards.iter().position(|deck| *deck == Deck::Chance).unwrap_or(0);
+        let deck = jailcards.remove(elem);
+
+        self.deck_mut(deck).push_back(Card::GetOutOfJail);
+    }
+
+    /// Perform the movement for a drawn card
+    fn action_card(&mut self, card: Card, reason: MoveReason) {
+        let position = self.player().position;
+
+        match self.rules.card_movement(card, &self.boarddef, position) {
+            CardMove::To(elem) if elem != position => {
+                // Collect salary if passing go
+                if self.rules.card_passes_go(card, position, elem) {
+                    self.pass_go(elem, 1);
+                }
+
+                // Remember the card for the rent payable on arrival
+                self.card_move = Some(card);
+
+                self.move_to(elem, reason)
+            }
+            CardMove::Jail => self.move_to(self.boarddef.find(Space::Visit), reason),
+            CardMove::To(_) | CardMove::Stay => (),
+        }
+    }
+
+    /// Roll the dice. The speed die is not rolled in jail
+    fn roll_dice(&mut self) -> Roll {
+        let in_jail = self.player().jailroll > 0;
+
+        self.dice.roll(&mut self.rng, in_jail)
     }
 }
 
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use super::stats::Stats;
+use crate::card::Deck;
+use crate::strategy::PlayerStrategy;
+
+/// Player token on the board
+#[derive(Debug, Clone)]
+pub struct Player {
+    pub(super) strategy: Arc<dyn PlayerStrategy>,
+    pub(super) position: usize,
+    pub(super) jailroll: u8,
+    pub(super) jailcards: Vec<Deck>,
+    pub(super) cash: u32,
+    pub(super) bankrupt: bool,
+    pub(super) stats: Stats,
+}
+
+impl Player {
+    /// Create a new player on Go with a given strategy
+    pub(super) fn new(spaces: usize, strategy: Arc<dyn PlayerStrategy>) -> Self {
+        Self {
+            strategy,
+            position: 0,
+            jailroll: 0,
+            jailcards: Vec::new(),
+            cash: 0,
+            bankrupt: false,
+            stats: Stats::new(spaces),
+        }
+    }
+
+    /// Returns the strategy used by the player
+    pub fn strategy(&self) -> &dyn PlayerStrategy {
+        self.strategy.as_ref()
+    }
+
+    /// Returns the current board position of the player
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns true if the player is in jail
+    pub fn in_jail(&self) -> bool {
+        self.jailroll > 0
+    }
+
+    /// Returns the decks of the get out of jail free cards held by the player
+    pub fn jail_cards(&self) -> &[Deck] {
+        &self.jailcards
+    }
+
+    /// Returns the cash held by the player
+    pub fn cash(&self) -> u32 {
+        self.cash
+    }
+
+    /// Returns true if the player is bankrupt and out of the game
+    pub fn is_bankrupt(&self) -> bool {
+        self.bankrupt
+    }
+
+    /// Returns the statistics for this player
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+}
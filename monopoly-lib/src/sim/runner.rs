@@ -0,0 +1,140 @@
+use std::{sync::Arc, thread};
+
+use rand::random;
+
+use crate::boarddef::BoardDefinition;
+use crate::dice::DiceModel;
+use crate::rules::Rules;
+use crate::strategy::PlayerStrategy;
+
+use super::stats::Stats;
+use super::{Board, SimRng};
+
+/// Runs independent simulations of the same game on several threads and merges their statistics
+#[derive(Debug, Clone)]
+pub struct ParallelRunner {
+    boarddef: BoardDefinition,
+    strategies: Vec<Arc<dyn PlayerStrategy>>,
+    randomcard: bool,
+    dice: DiceModel,
+    rules: Rules,
+    threads: usize,
+    seed: u64,
+}
+
+impl ParallelRunner {
+    /// Create a new runner with a given layout, player strategies (one per player), card selection method and
+    /// number of threads (0 to use all available) using a random seed
+    pub fn new(
+        boarddef: BoardDefinition,
+        strategies: Vec<Arc<dyn PlayerStrategy>>,
+        randomcard: bool,
+        threads: usize,
+    ) -> Self {
+        Self::with_seed(boarddef, strategies, randomcard, threads, random())
+    }
+
+    /// Create a new runner with a given layout, player strategies (one per player), card selection method and
+    /// number of threads (0 to use all available). Each thread's board is seeded from the seed given so runs with
+    /// the same seed and number of threads produce identical results
+    pub fn with_seed(
+        boarddef: BoardDefinition,
+        strategies: Vec<Arc<dyn PlayerStrategy>>,
+        randomcard: bool,
+        threads: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(!strategies.is_empty(), "At least one player is required");
+
+        let threads = if threads == 0 {
+            thread::available_parallelism().map_or(1, |n| n.get())
+        } else {
+            threads
+        };
+
+        Self {
+            boarddef,
+            strategies,
+            randomcard,
+            dice: DiceModel::classic(),
+            rules: Rules::classic(),
+            threads,
+            seed,
+        }
+    }
+
+    /// Use a different dice model on every thread
+    pub fn set_dice(&mut self, dice: DiceModel) {
+        self.dice = dice;
+    }
+
+    /// Use different rule variants on every thread
+    pub fn set_rules(&mut self, rules: Rules) {
+        rules.validate().expect("Invalid rules");
+
+        self.rules = rules;
+    }
+
+    /// Returns the number of threads used
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Returns the random number generator seed in use
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the random number generator seed used by the board on a thread
+    pub fn thread_seed(&self, thread: usize) -> u64 {
+        self.seed.wrapping_add(thread as u64)
+    }
+
+    /// Returns the number of turns taken by the board on a thread for a total number of turns
+    pub fn thread_turns(&self, thread: usize, turns: u64) -> u64 {
+        let threads = self.threads as u64;
+
+        turns / threads + ((thread as u64) < turns % threads) as u64
+    }
+
+    /// Takes a total number of turns split across the threads, returning the merged statistics for all players
+    pub fn run(&self, turns: u64) -> Stats {
+        let results = thread::scope(|scope| {
+            let handles = (0..self.threads)
+                .map(|thread| scope.spawn(move || self.run_thread(thread, turns)))
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Simulation thread panicked"))
+                .collect::<Vec<_>>()
+        });
+
+        let mut stats = Stats::new(self.boarddef.len());
+
+        for result in &results {
+            stats.merge(result);
+        }
+
+        stats
+    }
+
+    /// Runs the board on a thread
+    fn run_thread(&self, thread: usize, turns: u64) -> Stats {
+        let mut board: Board<SimRng> = Board::with_seed(
+            self.boarddef.clone(),
+            self.strategies.clone(),
+            self.randomcard,
+            self.thread_seed(thread),
+        );
+
+        board.set_dice(self.dice.clone());
+        board.set_rules(self.rules);
+
+        for _ in 0..self.thread_turns(thread, turns) {
+            board.turn();
+        }
+
+        board.stats().clone()
+    }
+}
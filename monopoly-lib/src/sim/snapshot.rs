@@ -0,0 +1,305 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs,
+    path::Path,
+    sync::Arc,
+};
+
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use super::economy::{BANK_HOTELS, BANK_HOUSES, Economy, HOTEL};
+use super::player::Player;
+use super::stats::Stats;
+use super::{Board, SimRng};
+use crate::boarddef::BoardDefinition;
+use crate::card::{Card, Deck};
+use crate::dice::DiceModel;
+use crate::rules::Rules;
+use crate::strategy::PlayerStrategy;
+
+/// Current snapshot format version. Increment when the format changes
+pub const SNAPSHOT_VERSION: u32 = 4;
+
+/// Saved state of a simulation which can be resumed later. Player strategies can't be saved so only their names
+/// are recorded, and matching strategies must be given when restoring
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    version: u32,
+    boarddef: BoardDefinition,
+    randomcard: bool,
+    dice: DiceModel,
+    rules: Rules,
+    seed: u64,
+    rng_seed: [u8; 32],
+    rng_stream: u64,
+    rng_word_pos: u128,
+    current: usize,
+    dice_total: u8,
+    ccdeck: Vec<Card>,
+    chdeck: Vec<Card>,
+    players: Vec<PlayerSnapshot>,
+    stats: Stats,
+    economy: Option<Economy>,
+}
+
+/// Saved state of a player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PlayerSnapshot {
+    strategy: String,
+    position: usize,
+    jailroll: u8,
+    jailcards: Vec<Deck>,
+    cash: u32,
+    bankrupt: bool,
+    stats: Stats,
+}
+
+impl Snapshot {
+    /// Returns the board layout in use
+    pub fn boarddef(&self) -> &BoardDefinition {
+        &self.boarddef
+    }
+
+    /// Returns the number of players
+    pub fn players(&self) -> usize {
+        self.players.len()
+    }
+
+    /// Returns the names of the strategies used by the players
+    pub fn strategy_names(&self) -> impl Iterator<Item = &str> {
+        self.players.iter().map(|player| player.strategy.as_str())
+    }
+
+    /// Returns the aggregate statistics for all players
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// Load a snapshot from a JSON file
+    pub fn load(file: &Path) -> Result<Self, SnapshotError> {
+        let contents = fs::read_to_string(file).map_err(SnapshotError::Io)?;
+
+        Self::from_json(&contents)
+    }
+
+    /// Save the snapshot to a JSON file
+    pub fn save(&self, file: &Path) -> Result<(), SnapshotError> {
+        fs::write(file, self.to_json()).map_err(SnapshotError::Io)
+    }
+
+    /// Parse a snapshot from a JSON string, checking the format version first
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(SnapshotError::Json)?;
+
+        match value.get("version").and_then(|v| v.as_u64()) {
+            Some(version) if version == SNAPSHOT_VERSION as u64 => (),
+            version => return Err(SnapshotError::Version(version)),
+        }
+
+        let snapshot: Self = serde_json::from_value(value).map_err(SnapshotError::Json)?;
+
+        snapshot.validate()?;
+
+        Ok(snapshot)
+    }
+
+    /// Convert the snapshot to a JSON string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Snapshot serialisation failed")
+    }
+
+    /// Check the saved state is consistent with the board layout
+    fn validate(&self) -> Result<(), SnapshotError> {
+        let spaces = self.boarddef.len();
+
+        let invalid = |msg: &str| Err(SnapshotError::Invalid(msg.to_string()));
+
+        self.rules
+            .validate()
+            .map_err(|e| SnapshotError::Invalid(e.to_string()))?;
+
+        if self.players.is_empty() {
+            return invalid("No players");
+        }
+
+        if self.current >= self.players.len() {
+            return invalid("Current player out of range");
+        }
+
+        if self.stats.arrivals().len() != spaces {
+            return invalid("Statistics don't match the board layout");
+        }
+
+        for player in &self.players {
+            if player.position >= spaces || player.stats.arrivals().len() != spaces {
+                return invalid("Player doesn't match the board layout");
+            }
+        }
+
+        if self.boarddef.chance().build_deck().len() != self.chdeck.len() + self.held(Deck::Chance)
+            || self.boarddef.community_chest().build_deck().len() != self.ccdeck.len() + self.held(Deck::CommunityChest)
+        {
+            return invalid("Card decks don't match the board layout");
+        }
+
+        if let Some(economy) = &self.economy {
+            if economy.owner.len() != spaces || economy.buildings.len() != spaces || economy.mortgaged.len() != spaces {
+                return invalid("Economy doesn't match the board layout");
+            }
+
+            if economy.buildings.iter().any(|&buildings| buildings > HOTEL) {
+                return invalid("Too many buildings on a space");
+            }
+
+            if economy.houses_built() > BANK_HOUSES || economy.hotels_built() > BANK_HOTELS {
+                return invalid("More buildings than the bank holds");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of get out of jail free cards from a deck held by the players
+    fn held(&self, deck: Deck) -> usize {
+        self.players
+            .iter()
+            .map(|player| player.jailcards.iter().filter(|d| **d == deck).count())
+            .sum()
+    }
+}
+
+impl Board<SimRng> {
+    /// Takes a snapshot of the simulation which can be saved and restored later
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            version: SNAPSHOT_VERSION,
+            boarddef: self.boarddef.clone(),
+            randomcard: self.randomcard,
+            dice: self.dice.clone(),
+            rules: self.rules,
+            seed: self.seed,
+            rng_seed: self.rng.get_seed(),
+            rng_stream: self.rng.get_stream(),
+            rng_word_pos: self.rng.get_word_pos(),
+            current: self.current,
+            dice_total: self.dice_total,
+            ccdeck: self.ccdeck.iter().copied().collect(),
+            chdeck: self.chdeck.iter().copied().collect(),
+            players: self
+                .players
+                .iter()
+                .map(|player| PlayerSnapshot {
+                    strategy: player.strategy.name().to_string(),
+                    position: player.position,
+                    jailroll: player.jailroll,
+                    jailcards: player.jailcards.clone(),
+                    cash: player.cash,
+                    bankrupt: player.bankrupt,
+                    stats: player.stats.clone(),
+                })
+                .collect(),
+            stats: self.stats.clone(),
+            economy: self.economy.clone(),
+        }
+    }
+
+    /// Restores a simulation from a snapshot, continuing exactly where it left off. A strategy must be given for
+    /// each player with the same name as the strategy in use when the snapshot was taken
+    pub fn restore(snapshot: Snapshot, strategies: Vec<Arc<dyn PlayerStrategy>>) -> Result<Self, SnapshotError> {
+        if strategies.len() != snapshot.players.len() {
+            return Err(SnapshotError::Invalid(format!(
+                "{} players saved but {} strategies given",
+                snapshot.players.len(),
+                strategies.len()
+            )));
+        }
+
+        let players = snapshot
+            .players
+            .into_iter()
+            .zip(strategies)
+            .enumerate()
+            .map(|(i, (player, strategy))| {
+                if player.strategy != strategy.name() {
+                    return Err(SnapshotError::Invalid(format!(
+                        "Player {} strategy '{}' saved but '{}' given",
+                        i + 1,
+                        player.strategy,
+                        strategy.name()
+                    )));
+                }
+
+                Ok(Player {
+                    strategy,
+                    position: player.position,
+                    jailroll: player.jailroll,
+                    jailcards: player.jailcards,
+                    cash: player.cash,
+                    bankrupt: player.bankrupt,
+                    stats: player.stats,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut rng = SimRng::from_seed(snapshot.rng_seed);
+        rng.set_stream(snapshot.rng_stream);
+        rng.set_word_pos(snapshot.rng_word_pos);
+
+        Ok(Self {
+            boarddef: snapshot.boarddef,
+            players,
+            current: snapshot.current,
+            ccdeck: snapshot.ccdeck.into(),
+            cccardchoose: if snapshot.randomcard {
+                Self::random_card
+            } else {
+                Self::next_card
+            },
+            chdeck: snapshot.chdeck.into(),
+            chcardchoose: if snapshot.randomcard {
+                Self::random_card
+            } else {
+                Self::next_card
+            },
+            randomcard: snapshot.randomcard,
+            dice: snapshot.dice,
+            rules: snapshot.rules,
+            stats: snapshot.stats,
+            economy: snapshot.economy,
+            dice_total: snapshot.dice_total,
+            card_move: None,
+            seed: snapshot.seed,
+            rng,
+            observer: None,
+            events: None,
+        })
+    }
+}
+
+/// Snapshot load error
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Version(Option<u64>),
+    Invalid(String),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "Unable to read or write snapshot: {e}"),
+            SnapshotError::Json(e) => write!(f, "Unable to parse snapshot: {e}"),
+            SnapshotError::Version(Some(version)) => write!(
+                f,
+                "Snapshot version {version} is not supported (expected {SNAPSHOT_VERSION})"
+            ),
+            SnapshotError::Version(None) => write!(f, "Snapshot has no version"),
+            SnapshotError::Invalid(msg) => write!(f, "Invalid snapshot: {msg}"),
+        }
+    }
+}
+
+impl Error for SnapshotError {}
@@ -0,0 +1,156 @@
+use crate::movereason::MoveReason;
+use serde::{Deserialize, Serialize};
+use strum::EnumCount;
+
+use super::batch::{Batches, ConfidenceInterval};
+
+/// Simulation statistics
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub(super) arrivals: Vec<u64>,
+    pub(super) arrival_reason: Vec<[u64; MoveReason::COUNT]>,
+    pub(super) moves: u64,
+    pub(super) turns: u64,
+    pub(super) doubles: [u64; 3],
+    pub(super) rollfreq: Vec<u64>,
+    pub(super) batches: Batches,
+}
+
+impl Stats {
+    /// Create empty statistics for a board with a given number of spaces
+    pub fn new(spaces: usize) -> Self {
+        Self {
+            arrivals: vec![0; spaces],
+            arrival_reason: vec![[0; MoveReason::COUNT]; spaces],
+            moves: 0,
+            turns: 0,
+            doubles: [0; 3],
+            rollfreq: vec![0; 11],
+            batches: Batches::new(spaces),
+        }
+    }
+
+    /// Returns reference to the space arrival count array
+    pub fn arrivals(&self) -> &[u64] {
+        &self.arrivals
+    }
+
+    /// Returns reference to the space arrival reason array
+    pub fn arrival_reasons(&self) -> &[[u64; MoveReason::COUNT]] {
+        &self.arrival_reason
+    }
+
+    /// Returns the number of moves made
+    pub fn moves(&self) -> u64 {
+        self.moves
+    }
+
+    /// Returns the number of turns taken (can involve >1 move)
+    pub fn turns(&self) -> u64 {
+        self.turns
+    }
+
+    /// Returns a reference to the doubles count array
+    pub fn doubles(&self) -> &[u64] {
+        &self.doubles
+    }
+
+    /// Returns a reference to the roll sum frequencies, starting at a total of 2
+    pub fn rollfreq(&self) -> &[u64] {
+        &self.rollfreq
+    }
+
+    /// Returns a reference to the arrival counts for batches of moves
+    pub fn batches(&self) -> &Batches {
+        &self.batches
+    }
+
+    /// Returns the arrival frequency for any of a set of spaces with a confidence interval at a confidence level
+    /// (eg. 0.95). Returns None if not enough moves have been made to estimate the interval
+    pub fn arrival_interval(&self, elems: &[usize], confidence: f64) -> Option<ConfidenceInterval> {
+        let std_error = self.batches.std_error(elems)?;
+
+        let estimate = elems.iter().map(|elem| self.arrivals[*elem]).sum::<u64>() as f64 / self.moves as f64;
+        let half_width = self.batches.t_quantile(confidence) * std_error;
+
+        Some(ConfidenceInterval {
+            estimate,
+            std_error,
+            lower: estimate - half_width,
+            upper: estimate + half_width,
+        })
+    }
+
+    /// Add the statistics from an independent simulation on the same board layout
+    pub fn merge(&mut self, other: &Stats) {
+        assert_eq!(self.arrivals.len(), other.arrivals.len(), "Board layouts differ");
+
+        for (a, b) in self.arrivals.iter_mut().zip(&other.arrivals) {
+            *a += b;
+        }
+
+        for (a, b) in self.arrival_reason.iter_mut().zip(&other.arrival_reason) {
+            for (a, b) in a.iter_mut().zip(b) {
+                *a += b;
+            }
+        }
+
+        self.moves += other.moves;
+        self.turns += other.turns;
+
+        for (a, b) in self.doubles.iter_mut().zip(&other.doubles) {
+            *a += b;
+        }
+
+        if self.rollfreq.len() < other.rollfreq.len() {
+            self.rollfreq.resize(other.rollfreq.len(), 0);
+        }
+
+        for (a, b) in self.rollfreq.iter_mut().zip(&other.rollfreq) {
+            *a += b;
+        }
+
+        self.batches.merge(&other.batches);
+    }
+
+    /// Record the start of a turn
+    pub(super) fn record_turn(&mut self) {
+        self.turns += 1;
+    }
+
+    /// Record a dice roll total
+    pub(super) fn record_roll(&mut self, total: u8) {
+        let elem = total as usize - 2;
+
+        // Extend for dice with larger totals
+        if elem >= self.rollfreq.len() {
+            self.rollfreq.resize(elem + 1, 0);
+        }
+
+        self.rollfreq[elem] += 1;
+    }
+
+    /// Record the number of doubles thrown in a turn
+    pub(super) fn record_doubles(&mut self, doubles: usize) {
+        if doubles > 0 {
+            self.doubles[doubles - 1] += 1;
+        }
+    }
+
+    /// Record an arrival on a space
+    pub(super) fn record_arrival(&mut self, elem: usize, reason: MoveReason) {
+        // Record arrival at this space
+        self.arrivals[elem] += 1;
+
+        // Record move
+        self.moves += 1;
+        self.batches.record_arrival(elem);
+
+        // Record move reason (all except Rolled)
+        let reason_elem = reason as isize;
+
+        if reason_elem >= 0 {
+            self.arrival_reason[elem][reason_elem as usize] += 1;
+        }
+    }
+}
@@ -1,70 +1,81 @@
+use super::economy::{GO_SALARY, HOTEL, JAIL_FINE};
 use super::*;
+use crate::space::PropertySet;
+use crate::strategy::{GameView, HouseShortage, JailWait, SetTrader, SetTraderError, Trade};
+
+/// Creates a list of player strategies
+fn players<S: PlayerStrategy + Copy + 'static>(count: usize, strategy: S) -> Vec<Arc<dyn PlayerStrategy>> {
+    (0..count)
+        .map(|_| Arc::new(strategy) as Arc<dyn PlayerStrategy>)
+        .collect()
+}
 
 #[test]
 fn test_jail_rolls_fail() {
-    let mut board = Board::new(Strategy::JailWait, false);
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
 
-    let g2j = Space::find(Space::GoToJail);
-    let visit = Space::find(Space::Visit);
+    let g2j = board.boarddef.find(Space::GoToJail);
+    let visit = board.boarddef.find(Space::Visit);
 
     // Position 2 spaces before go to jail
-    board.position = g2j - 2;
+    board.players[0].position = g2j - 2;
 
     // Roll double 2 - go to jail
     board.turn_with_dice(|_board, _doubles| (1, 1));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrivals[g2j], 1);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 1);
 
     // Roll to get out (fail) - still in jail
     board.turn_with_dice(|_board, _doubles| (1, 2));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 2);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::NoDouble as usize], 1);
-    assert_eq!(board.arrivals[g2j], 2);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 2);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 2);
 
     // Roll to get out (fail) - still in jail
     board.turn_with_dice(|_board, _doubles| (1, 2));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 3);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
-    assert_eq!(board.arrivals[g2j], 3);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 3);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
+    assert_eq!(board.stats.arrivals[g2j], 3);
 
     // Roll to get out (fail) - should now be on just visiting
     board.turn_with_dice(|_board, _doubles| (1, 2));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 0);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
-    assert_eq!(board.arrival_reason[visit][MoveReason::ExitJail as usize], 1);
-    assert_eq!(board.arrivals[g2j], 3);
-    assert_eq!(board.arrivals[visit], 1);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 0);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
+    assert_eq!(board.stats.arrival_reason[visit][MoveReason::ExitJail as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 3);
+    assert_eq!(board.stats.arrivals[visit], 1);
 
     // Roll to move - should now be moved
     board.turn_with_dice(|_board, _doubles| (1, 2));
 
-    assert_eq!(board.position, visit + 3);
-    assert_eq!(board.jailroll, 0);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
-    assert_eq!(board.arrival_reason[visit][MoveReason::ExitJail as usize], 1);
-    assert_eq!(board.arrivals[g2j], 3);
-    assert_eq!(board.arrivals[visit], 1);
-    assert_eq!(board.arrivals[visit + 3], 1);
+    assert_eq!(board.players[0].position, visit + 3);
+    assert_eq!(board.players[0].jailroll, 0);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
+    assert_eq!(board.stats.arrival_reason[visit][MoveReason::ExitJail as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 3);
+    assert_eq!(board.stats.arrivals[visit], 1);
+    assert_eq!(board.stats.arrivals[visit + 3], 1);
 
     // Check counts
-    assert_eq!(board.turns, 5);
-    assert_eq!(board.moves, 5);
-    assert_eq!(board.arrivals.iter().sum::<u64>(), board.moves);
+    assert_eq!(board.stats.turns, 5);
+    assert_eq!(board.stats.moves, 5);
+    assert_eq!(board.stats.arrivals.iter().sum::<u64>(), board.stats.moves);
     assert_eq!(
         board
+            .stats
             .arrival_reason
             .iter()
             .map(|reasons| reasons.iter().sum::<u64>())
@@ -75,21 +86,21 @@ fn test_jail_rolls_fail() {
 
 #[test]
 fn test_jail_rolls_succ1() {
-    let mut board = Board::new(Strategy::JailWait, false);
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
 
-    let g2j = Space::find(Space::GoToJail);
-    let visit = Space::find(Space::Visit);
+    let g2j = board.boarddef.find(Space::GoToJail);
+    let visit = board.boarddef.find(Space::Visit);
 
     // Position 2 spaces before go to jail
-    board.position = g2j - 2;
+    board.players[0].position = g2j - 2;
 
     // Roll double 2
     board.turn_with_dice(|_board, _doubles| (1, 1));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrivals[g2j], 1);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 1);
 
     // Roll to get out (success)
     board.turn_with_dice(|_board, doubles| {
@@ -98,18 +109,19 @@ fn test_jail_rolls_succ1() {
         (2, 2)
     });
 
-    assert_eq!(board.position, visit + 4);
-    assert_eq!(board.jailroll, 0);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrivals[g2j], 1);
-    assert_eq!(board.arrivals[visit + 4], 1);
+    assert_eq!(board.players[0].position, visit + 4);
+    assert_eq!(board.players[0].jailroll, 0);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 1);
+    assert_eq!(board.stats.arrivals[visit + 4], 1);
 
     // Check counts
-    assert_eq!(board.turns, 2);
-    assert_eq!(board.moves, 2);
-    assert_eq!(board.arrivals.iter().sum::<u64>(), board.moves);
+    assert_eq!(board.stats.turns, 2);
+    assert_eq!(board.stats.moves, 2);
+    assert_eq!(board.stats.arrivals.iter().sum::<u64>(), board.stats.moves);
     assert_eq!(
         board
+            .stats
             .arrival_reason
             .iter()
             .map(|reasons| reasons.iter().sum::<u64>())
@@ -120,30 +132,30 @@ fn test_jail_rolls_succ1() {
 
 #[test]
 fn test_jail_rolls_succ2() {
-    let mut board = Board::new(Strategy::JailWait, false);
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
 
-    let g2j = Space::find(Space::GoToJail);
-    let visit = Space::find(Space::Visit);
+    let g2j = board.boarddef.find(Space::GoToJail);
+    let visit = board.boarddef.find(Space::Visit);
 
     // Position 2 spaces before go to jail
-    board.position = g2j - 2;
+    board.players[0].position = g2j - 2;
 
     // Roll double 2
     board.turn_with_dice(|_board, _doubles| (1, 1));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrivals[g2j], 1);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 1);
 
     // Roll to get out (fail) - still in jail
     board.turn_with_dice(|_board, _doubles| (1, 2));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 2);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::NoDouble as usize], 1);
-    assert_eq!(board.arrivals[g2j], 2);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 2);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 2);
 
     // Roll to get out (success)
     board.turn_with_dice(|_board, doubles| {
@@ -152,19 +164,20 @@ fn test_jail_rolls_succ2() {
         (2, 2)
     });
 
-    assert_eq!(board.position, visit + 4);
-    assert_eq!(board.jailroll, 0);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::NoDouble as usize], 1);
-    assert_eq!(board.arrivals[g2j], 2);
-    assert_eq!(board.arrivals[visit + 4], 1);
+    assert_eq!(board.players[0].position, visit + 4);
+    assert_eq!(board.players[0].jailroll, 0);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 2);
+    assert_eq!(board.stats.arrivals[visit + 4], 1);
 
     // Check counts
-    assert_eq!(board.turns, 3);
-    assert_eq!(board.moves, 3);
-    assert_eq!(board.arrivals.iter().sum::<u64>(), board.moves);
+    assert_eq!(board.stats.turns, 3);
+    assert_eq!(board.stats.moves, 3);
+    assert_eq!(board.stats.arrivals.iter().sum::<u64>(), board.stats.moves);
     assert_eq!(
         board
+            .stats
             .arrival_reason
             .iter()
             .map(|reasons| reasons.iter().sum::<u64>())
@@ -175,39 +188,39 @@ fn test_jail_rolls_succ2() {
 
 #[test]
 fn test_jail_rolls_succ3() {
-    let mut board = Board::new(Strategy::JailWait, false);
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
 
-    let g2j = Space::find(Space::GoToJail);
-    let visit = Space::find(Space::Visit);
+    let g2j = board.boarddef.find(Space::GoToJail);
+    let visit = board.boarddef.find(Space::Visit);
 
     // Position 2 spaces before go to jail
-    board.position = g2j - 2;
+    board.players[0].position = g2j - 2;
 
     // Roll double 2
     board.turn_with_dice(|_board, _doubles| (1, 1));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrivals[g2j], 1);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 1);
 
     // Roll to get out (fail) - still in jail
     board.turn_with_dice(|_board, _doubles| (1, 2));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 2);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::NoDouble as usize], 1);
-    assert_eq!(board.arrivals[g2j], 2);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 2);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 1);
+    assert_eq!(board.stats.arrivals[g2j], 2);
 
     // Roll to get out (fail) - still in jail
     board.turn_with_dice(|_board, _doubles| (1, 2));
 
-    assert_eq!(board.position, visit);
-    assert_eq!(board.jailroll, 3);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
-    assert_eq!(board.arrivals[g2j], 3);
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 3);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
+    assert_eq!(board.stats.arrivals[g2j], 3);
 
     // Roll to get out (success)
     board.turn_with_dice(|_board, doubles| {
@@ -216,19 +229,20 @@ fn test_jail_rolls_succ3() {
         (2, 2)
     });
 
-    assert_eq!(board.position, visit + 4);
-    assert_eq!(board.jailroll, 0);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
-    assert_eq!(board.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
-    assert_eq!(board.arrivals[g2j], 3);
-    assert_eq!(board.arrivals[visit + 4], 1);
+    assert_eq!(board.players[0].position, visit + 4);
+    assert_eq!(board.players[0].jailroll, 0);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::GoToJail as usize], 1);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 2);
+    assert_eq!(board.stats.arrivals[g2j], 3);
+    assert_eq!(board.stats.arrivals[visit + 4], 1);
 
     // Check counts
-    assert_eq!(board.turns, 4);
-    assert_eq!(board.moves, 4);
-    assert_eq!(board.arrivals.iter().sum::<u64>(), board.moves);
+    assert_eq!(board.stats.turns, 4);
+    assert_eq!(board.stats.moves, 4);
+    assert_eq!(board.stats.arrivals.iter().sum::<u64>(), board.stats.moves);
     assert_eq!(
         board
+            .stats
             .arrival_reason
             .iter()
             .map(|reasons| reasons.iter().sum::<u64>())
@@ -239,30 +253,31 @@ fn test_jail_rolls_succ3() {
 
 #[test]
 fn test_chance_to_cc() {
-    let mut board = Board::new(Strategy::JailWait, false);
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
 
-    let ch3 = Space::find(Space::Chance(2));
-    let cc3 = Space::find(Space::CommunityChest(2));
+    let ch3 = board.boarddef.find(Space::Chance(2));
+    let cc3 = board.boarddef.find(Space::CommunityChest(2));
 
     // Position 5 spaces before chance 3
-    board.position = ch3 - 5;
+    board.players[0].position = ch3 - 5;
 
-    board.chcardchoose = |_rng, _deck| CHCard::Back3;
-    board.cccardchoose = |_rng, _deck| CCCard::Inconsequential;
+    board.chcardchoose = |_rng, _deck| Card::Back(3);
+    board.cccardchoose = |_rng, _deck| Card::Inconsequential;
 
     // Roll 5 to land on chance which will send us back 3 to the community chest
     board.turn_with_dice(|_board, _doubles| (2, 3));
 
-    assert_eq!(board.position, cc3);
-    assert_eq!(board.arrival_reason[cc3][MoveReason::CHCard as usize], 1);
-    assert_eq!(board.arrivals[cc3], 1);
+    assert_eq!(board.players[0].position, cc3);
+    assert_eq!(board.stats.arrival_reason[cc3][MoveReason::CHCard as usize], 1);
+    assert_eq!(board.stats.arrivals[cc3], 1);
 
     // Check counts
-    assert_eq!(board.turns, 1);
-    assert_eq!(board.moves, 1);
-    assert_eq!(board.arrivals.iter().sum::<u64>(), board.moves);
+    assert_eq!(board.stats.turns, 1);
+    assert_eq!(board.stats.moves, 1);
+    assert_eq!(board.stats.arrivals.iter().sum::<u64>(), board.stats.moves);
     assert_eq!(
         board
+            .stats
             .arrival_reason
             .iter()
             .map(|reasons| reasons.iter().sum::<u64>())
@@ -273,30 +288,31 @@ fn test_chance_to_cc() {
 
 #[test]
 fn test_chance_to_cc_to_go() {
-    let mut board = Board::new(Strategy::JailWait, false);
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
 
-    let go = Space::find(Space::Go);
-    let ch3 = Space::find(Space::Chance(2));
+    let go = board.boarddef.find(Space::Go);
+    let ch3 = board.boarddef.find(Space::Chance(2));
 
     // Position 5 spaces before chance 3
-    board.position = ch3 - 5;
+    board.players[0].position = ch3 - 5;
 
-    board.chcardchoose = |_rng, _deck| CHCard::Back3;
-    board.cccardchoose = |_rng, _deck| CCCard::GoGo;
+    board.chcardchoose = |_rng, _deck| Card::Back(3);
+    board.cccardchoose = |_rng, _deck| Card::GoTo(Space::Go);
 
     // Roll 5 to land on chance which will send us back 3 to the community chest which will then send us to Go
     board.turn_with_dice(|_board, _doubles| (2, 3));
 
-    assert_eq!(board.position, go);
-    assert_eq!(board.arrival_reason[go][MoveReason::CCCard as usize], 1);
-    assert_eq!(board.arrivals[go], 1);
+    assert_eq!(board.players[0].position, go);
+    assert_eq!(board.stats.arrival_reason[go][MoveReason::CHCardCCCard as usize], 1);
+    assert_eq!(board.stats.arrivals[go], 1);
 
     // Check counts
-    assert_eq!(board.turns, 1);
-    assert_eq!(board.moves, 1);
-    assert_eq!(board.arrivals.iter().sum::<u64>(), board.moves);
+    assert_eq!(board.stats.turns, 1);
+    assert_eq!(board.stats.moves, 1);
+    assert_eq!(board.stats.arrivals.iter().sum::<u64>(), board.stats.moves);
     assert_eq!(
         board
+            .stats
             .arrival_reason
             .iter()
             .map(|reasons| reasons.iter().sum::<u64>())
@@ -304,3 +320,1420 @@ fn test_chance_to_cc_to_go() {
         1
     );
 }
+
+#[test]
+fn test_cc_to_chance() {
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
+
+    let cc2 = board.boarddef.find(Space::CommunityChest(1));
+    let ch2 = board.boarddef.find(Space::Chance(1));
+
+    // Position 3 spaces before community chest 2
+    board.players[0].position = cc2 - 3;
+
+    board.cccardchoose = |_rng, _deck| Card::GoNext(PropertySet::Chance);
+    board.chcardchoose = |_rng, _deck| Card::Inconsequential;
+
+    // Roll 3 to land on community chest which will send us to the next chance
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.players[0].position, ch2);
+    assert_eq!(board.stats.arrival_reason[ch2][MoveReason::CCCard as usize], 1);
+    assert_eq!(board.stats.arrivals[ch2], 1);
+
+    // Check counts
+    assert_eq!(board.stats.turns, 1);
+    assert_eq!(board.stats.moves, 1);
+    assert_eq!(board.stats.arrivals.iter().sum::<u64>(), board.stats.moves);
+}
+
+#[test]
+fn test_seeded() {
+    let run = |seed| {
+        let mut board: Board = Board::with_seed(BoardDefinition::default(), players(1, JailWait), true, seed);
+
+        for _ in 0..10_000 {
+            board.turn();
+        }
+
+        board
+    };
+
+    let board1 = run(1234);
+    let board2 = run(1234);
+
+    // Boards with the same seed should give identical results
+    assert_eq!(board1.seed(), 1234);
+    assert_eq!(board1.arrivals(), board2.arrivals());
+    assert_eq!(board1.arrival_reasons(), board2.arrival_reasons());
+    assert_eq!(board1.doubles(), board2.doubles());
+    assert_eq!(board1.rollfreq(), board2.rollfreq());
+    assert_eq!(board1.moves(), board2.moves());
+
+    // Different seeds should give different results
+    let board3 = run(4321);
+
+    assert_ne!(board1.arrivals(), board3.arrivals());
+}
+
+#[test]
+fn test_players_take_turns() {
+    let mut board = Board::new(BoardDefinition::default(), players(3, JailWait), false);
+
+    // Each player rolls 3 in turn
+    for player in 0..3 {
+        assert_eq!(board.current_player(), player);
+
+        board.turn_with_dice(|_board, _doubles| (1, 2));
+    }
+
+    // Back to the first player
+    assert_eq!(board.current_player(), 0);
+
+    for player in board.players() {
+        assert_eq!(player.position(), 3);
+        assert_eq!(player.stats().turns(), 1);
+        assert_eq!(player.stats().arrivals()[3], 1);
+    }
+
+    // Check aggregate counts
+    assert_eq!(board.turns(), 3);
+    assert_eq!(board.moves(), 3);
+    assert_eq!(board.arrivals_on(3), 3);
+    assert_eq!(board.rollfreq()[1], 3);
+}
+
+#[test]
+fn test_shared_deck() {
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    let ch1 = board.boarddef.find(Space::Chance(0));
+
+    // Set up the chance deck
+    board.chdeck = VecDeque::from([Card::GoTo(Space::Go), Card::Back(3)]);
+
+    // First player lands on chance 1 and draws advance to go
+    board.turn_with_dice(|_board, _doubles| (3, 4));
+
+    assert_eq!(board.players[0].position, 0);
+
+    // Second player lands on chance 1 and draws the next card from the same deck
+    board.turn_with_dice(|_board, _doubles| (3, 4));
+
+    assert_eq!(board.players[1].position, ch1 - 3);
+
+    // Check per player and aggregate arrival reasons
+    assert_eq!(board.players[0].stats.arrival_reason[0][MoveReason::CHCard as usize], 1);
+    assert_eq!(
+        board.players[1].stats.arrival_reason[ch1 - 3][MoveReason::CHCard as usize],
+        1
+    );
+    assert_eq!(board.stats.arrival_reason[0][MoveReason::CHCard as usize], 1);
+    assert_eq!(board.stats.arrival_reason[ch1 - 3][MoveReason::CHCard as usize], 1);
+    assert_eq!(board.moves(), 2);
+}
+
+#[test]
+fn test_economy_buy_and_rent() {
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    board.enable_economy(1500);
+
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+
+    // First player rolls 3 and buys the second brown property
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.players[0].position, b2);
+    assert_eq!(board.players[0].cash, 1440);
+    assert_eq!(board.economy().unwrap().owner(b2), Some(0));
+
+    // Second player rolls 3 and pays rent
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.players[1].cash, 1496);
+    assert_eq!(board.players[0].cash, 1444);
+}
+
+#[test]
+fn test_economy_rent() {
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    board.enable_economy(1500);
+
+    let boarddef = board.boarddef.clone();
+    let b1 = boarddef.find(Space::Property(0, 0));
+    let b2 = boarddef.find(Space::Property(0, 1));
+    let r1 = boarddef.find(Space::Rail(0));
+    let r2 = boarddef.find(Space::Rail(1));
+    let u1 = boarddef.find(Space::Utility(0));
+    let u2 = boarddef.find(Space::Utility(1));
+
+    let economy = board.economy.as_mut().unwrap();
+
+    // Single brown property
+    economy.owner[b1] = Some(0);
+
+    assert_eq!(economy.rent(&boarddef, b1, 7), 2);
+
+    // Both brown properties - rent doubled
+    economy.owner[b2] = Some(0);
+
+    assert_eq!(economy.rent(&boarddef, b1, 7), 4);
+
+    // Houses
+    economy.buildings[b1] = 1;
+
+    assert_eq!(economy.rent(&boarddef, b1, 7), 10);
+    assert!(!economy.can_build(&boarddef, 0, b1));
+    assert!(economy.can_build(&boarddef, 0, b2));
+
+    // Stations
+    economy.owner[r1] = Some(1);
+
+    assert_eq!(economy.rent(&boarddef, r1, 7), 25);
+
+    economy.owner[r2] = Some(1);
+
+    assert_eq!(economy.rent(&boarddef, r1, 7), 50);
+
+    // Utilities
+    economy.owner[u1] = Some(1);
+
+    assert_eq!(economy.rent(&boarddef, u1, 7), 28);
+
+    economy.owner[u2] = Some(1);
+
+    assert_eq!(economy.rent(&boarddef, u1, 7), 70);
+}
+
+#[test]
+fn test_card_rent() {
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    board.enable_economy(1500);
+
+    let boarddef = board.boarddef.clone();
+    let ch1 = boarddef.find(Space::Chance(0));
+    let r1 = boarddef.find(Space::Rail(0));
+    let u1 = boarddef.find(Space::Utility(0));
+
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.owner[r1] = Some(1);
+    economy.owner[u1] = Some(1);
+
+    // Double station rent and ten times the dice for a utility
+    let station = Card::GoNext(PropertySet::Station);
+    let utility = Card::GoNext(PropertySet::Utility);
+
+    assert_eq!(economy.card_rent(&boarddef, r1, station, 7), 50);
+    assert_eq!(economy.card_rent(&boarddef, u1, utility, 7), 70);
+    assert_eq!(economy.card_rent(&boarddef, u1, Card::Back(3), 7), 28);
+
+    economy.mortgaged[r1] = true;
+
+    assert_eq!(economy.card_rent(&boarddef, r1, station, 7), 0);
+
+    // Advance to the nearest utility and throw the dice again
+    board.chcardchoose = |_rng, _deck| Card::GoNext(PropertySet::Utility);
+
+    board.turn_with_dice(|_board, _doubles| (3, 4));
+
+    let paid = board.players[1].cash - 1500;
+
+    assert_eq!(ch1, 7);
+    assert_eq!(board.players[0].position, u1);
+    assert_eq!(board.players[0].cash, 1500 - paid);
+    assert!(paid % 10 == 0 && (20..=120).contains(&paid));
+}
+
+#[test]
+fn test_economy_build() {
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    board.enable_economy(700);
+
+    let b1 = board.boarddef.find(Space::Property(0, 0));
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.owner[b1] = Some(0);
+    economy.owner[b2] = Some(0);
+
+    // Build keeping cash in reserve
+    board.build();
+
+    assert_eq!(board.players[0].cash, 200);
+    assert_eq!(board.economy().unwrap().buildings(b1), 5);
+    assert_eq!(board.economy().unwrap().buildings(b2), 5);
+}
+
+#[test]
+fn test_economy_bank_limits() {
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    board.enable_economy(10_000);
+
+    let b1 = board.boarddef.find(Space::Property(0, 0));
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+
+    let others = (0..board.boarddef.len())
+        .filter(|&elem| board.boarddef[elem].house_cost().is_some() && elem != b1 && elem != b2)
+        .collect::<Vec<_>>();
+
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.owner[b1] = Some(0);
+    economy.owner[b2] = Some(0);
+
+    // The other player has bought every house
+    for &elem in &others[..8] {
+        economy.owner[elem] = Some(1);
+        economy.buildings[elem] = 4;
+    }
+
+    assert_eq!(economy.houses_available(), 0);
+    assert!(!economy.can_build(&board.boarddef, 0, b1));
+
+    board.build();
+
+    assert_eq!(board.players[0].cash, 10_000);
+
+    // Building a hotel returns four houses to the bank
+    board.economy.as_mut().unwrap().buildings[others[0]] = HOTEL;
+
+    board.build();
+
+    assert_eq!(board.players[0].cash, 10_000 - 200);
+    assert_eq!(board.economy().unwrap().buildings(b1), 2);
+    assert_eq!(board.economy().unwrap().buildings(b2), 2);
+    assert_eq!(board.economy().unwrap().houses_available(), 0);
+
+    // Hotels can't be built when the bank has none left
+    let economy = board.economy.as_mut().unwrap();
+
+    for &elem in &others[..12] {
+        economy.buildings[elem] = HOTEL;
+    }
+
+    economy.buildings[b1] = 4;
+    economy.buildings[b2] = 4;
+
+    assert_eq!(economy.hotels_available(), 0);
+    assert_eq!(economy.houses_available(), 24);
+    assert!(!economy.can_build(&board.boarddef, 0, b1));
+}
+
+#[test]
+fn test_economy_sell() {
+    use event::Event;
+
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    board.enable_economy(0);
+
+    let b1 = board.boarddef.find(Space::Property(0, 0));
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+
+    let others = (0..board.boarddef.len())
+        .filter(|&elem| board.boarddef[elem].house_cost().is_some() && elem != b1 && elem != b2)
+        .collect::<Vec<_>>();
+
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.owner[b1] = Some(0);
+    economy.owner[b2] = Some(0);
+    economy.buildings[b1] = HOTEL;
+    economy.buildings[b2] = HOTEL;
+
+    // Leave two houses in the bank
+    for &elem in &others[..8] {
+        economy.owner[elem] = Some(1);
+        economy.buildings[elem] = 4;
+    }
+
+    economy.buildings[others[7]] = 2;
+
+    assert!(economy.can_sell(&board.boarddef, 0, b1));
+    assert!(!economy.can_sell(&board.boarddef, 1, b1));
+
+    // Selling a hotel during a house shortage sells the missing houses as well
+    board.sell_building(b1);
+
+    assert_eq!(board.players[0].cash, 25 * 3);
+    assert_eq!(board.economy().unwrap().buildings(b1), 2);
+
+    // Buildings must be sold evenly
+    let economy = board.economy().unwrap();
+
+    assert!(!economy.can_sell(&board.boarddef, 0, b1));
+    assert!(economy.can_sell(&board.boarddef, 0, b2));
+
+    board.sell_building(b2);
+
+    assert_eq!(board.players[0].cash, 25 * 8);
+    assert_eq!(board.economy().unwrap().buildings(b2), 0);
+
+    // Buildings are sold to raise cash when paying
+    board.players[0].cash = 10;
+    board.events = Some(Vec::new());
+
+    board.pay(55, Some(1));
+
+    assert!(!board.players[0].bankrupt);
+    assert_eq!(board.players[0].cash, 5);
+    assert_eq!(board.players[1].cash, 55);
+    assert_eq!(board.economy().unwrap().buildings(b1), 0);
+    assert!(board.events.unwrap().contains(&Event::BuildingSold {
+        player: 0,
+        space: b1,
+        buildings: 0
+    }));
+}
+
+#[test]
+fn test_house_shortage() {
+    let strategies: Vec<Arc<dyn PlayerStrategy>> = vec![Arc::new(HouseShortage::new(JailWait)), Arc::new(JailWait)];
+    let mut board = Board::new(BoardDefinition::default(), strategies, false);
+
+    board.enable_economy(2_000);
+
+    let b1 = board.boarddef.find(Space::Property(0, 0));
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.owner[b1] = Some(0);
+    economy.owner[b2] = Some(0);
+
+    // Four houses are built on each space but no hotels
+    board.build();
+
+    assert_eq!(board.players[0].cash, 2_000 - 400);
+    assert_eq!(board.economy().unwrap().buildings(b1), 4);
+    assert_eq!(board.economy().unwrap().buildings(b2), 4);
+    assert_eq!(board.economy().unwrap().houses_available(), 24);
+    assert_eq!(board.players[0].strategy.name(), "Roll to leave jail (house shortage)");
+}
+
+#[test]
+fn test_auction() {
+    use event::Event;
+
+    let boarddef = BoardDefinition::default();
+    let probs = vec![1.0; boarddef.len()];
+
+    // Probabilities must be given for every space on the board
+    assert_eq!(
+        SetTrader::new(JailWait, &boarddef, vec![1.0; 3]).err(),
+        Some(SetTraderError::Length {
+            probs: 3,
+            spaces: boarddef.len()
+        })
+    );
+
+    let strategies = (0..3)
+        .map(|_| Arc::new(SetTrader::new(JailWait, &boarddef, probs.clone()).unwrap()) as Arc<dyn PlayerStrategy>)
+        .collect();
+
+    let mut board = Board::new(boarddef, strategies, false);
+
+    board.enable_economy(1000);
+
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+
+    // First player can't afford the space so it is auctioned
+    board.players[0].cash = 0;
+    board.players[2].cash = 45;
+    board.events = Some(Vec::new());
+
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.players[0].position, b2);
+    assert_eq!(board.economy().unwrap().owner(b2), Some(1));
+    assert_eq!(board.players[1].cash, 950);
+    assert_eq!(board.players[2].cash, 45);
+
+    let events = board.events.take().unwrap();
+
+    assert!(events.contains(&Event::Bid {
+        player: 2,
+        space: b2,
+        amount: 40
+    }));
+    assert!(events.contains(&Event::Auctioned {
+        space: b2,
+        winner: Some(1),
+        price: 50
+    }));
+
+    // Nobody bids with the default strategy
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    board.enable_economy(0);
+    board.events = Some(Vec::new());
+
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.economy().unwrap().owner(b2), None);
+    assert!(board.events.unwrap().contains(&Event::Auctioned {
+        space: b2,
+        winner: None,
+        price: 0
+    }));
+}
+
+#[test]
+fn test_trade() {
+    use event::Event;
+
+    let boarddef = BoardDefinition::default();
+    let probs = vec![1.0; boarddef.len()];
+
+    let strategies = (0..2)
+        .map(|_| Arc::new(SetTrader::new(JailWait, &boarddef, probs.clone()).unwrap()) as Arc<dyn PlayerStrategy>)
+        .collect();
+
+    let mut board = Board::new(boarddef, strategies, false);
+
+    board.enable_economy(1000);
+
+    let b1 = board.boarddef.find(Space::Property(0, 0));
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+    let r1 = board.boarddef.find(Space::Rail(0));
+
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.owner[b1] = Some(0);
+    economy.owner[b2] = Some(1);
+    economy.owner[r1] = Some(1);
+
+    // Invalid trades
+    let invalid = [
+        Trade {
+            partner: 0,
+            take: vec![b1],
+            ..Default::default()
+        },
+        Trade {
+            partner: 1,
+            give: vec![r1],
+            ..Default::default()
+        },
+        Trade {
+            partner: 1,
+            give: vec![b1],
+            take: vec![b1],
+            ..Default::default()
+        },
+        Trade {
+            partner: 1,
+            take: vec![b2],
+            give_cash: 2000,
+            ..Default::default()
+        },
+        Trade {
+            partner: 1,
+            ..Default::default()
+        },
+    ];
+
+    for trade in invalid {
+        assert!(!board.propose_trade(trade));
+    }
+
+    // Declined trade
+    assert!(!board.propose_trade(Trade {
+        partner: 1,
+        take: vec![r1],
+        give_cash: 10,
+        ..Default::default()
+    }));
+
+    assert_eq!(board.economy().unwrap().owner(r1), Some(1));
+
+    // The first player buys the space needed to complete the set for twice its value
+    board.events = Some(Vec::new());
+    board.trade();
+
+    let trade = Trade {
+        partner: 1,
+        take: vec![b2],
+        give_cash: 120,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        board.events.take().unwrap(),
+        vec![Event::Traded {
+            player: 0,
+            trade,
+            accepted: true
+        }]
+    );
+    assert_eq!(board.economy().unwrap().owner(b2), Some(0));
+    assert_eq!(board.players[0].cash, 880);
+    assert_eq!(board.players[1].cash, 1120);
+
+    // Spaces can't be traded when their set has buildings
+    board.economy.as_mut().unwrap().buildings[b2] = 1;
+
+    assert!(!board.economy().unwrap().can_trade(&board.boarddef, 0, b1));
+
+    // The receiver of a mortgaged space pays the interest and must be able to afford it
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.buildings[b2] = 0;
+    economy.mortgaged[b1] = true;
+
+    let trade = Trade {
+        partner: 1,
+        give: vec![b1],
+        ..Default::default()
+    };
+
+    board.players[1].cash = 2;
+
+    assert!(!board.propose_trade(trade.clone()));
+
+    board.players[1].cash = 1120;
+
+    assert!(board.propose_trade(trade));
+    assert_eq!(board.economy().unwrap().owner(b1), Some(1));
+    assert!(board.economy().unwrap().is_mortgaged(b1));
+    assert_eq!(board.players[0].cash, 880);
+    assert_eq!(board.players[1].cash, 1120 - 3);
+}
+
+#[test]
+fn test_economy_go_and_tax() {
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
+
+    board.enable_economy(1500);
+
+    let t1 = board.boarddef.find(Space::Tax(0));
+
+    board.cccardchoose = |_rng, _deck| Card::Inconsequential;
+
+    // Pass go
+    board.players[0].position = board.boarddef.len() - 1;
+
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.players[0].position, 2);
+    assert_eq!(board.players[0].cash, 1700);
+
+    // Pay tax
+    board.players[0].position = t1 - 3;
+
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.players[0].position, t1);
+    assert_eq!(board.players[0].cash, 1500);
+}
+
+#[test]
+fn test_economy_short_board_laps() {
+    use crate::card::DeckDefinition;
+
+    // Board shorter than the largest roll of the dice
+    let boarddef = BoardDefinition::new(
+        "Short",
+        vec![
+            Space::Go,
+            Space::Property(0, 0),
+            Space::Property(0, 1),
+            Space::Rail(0),
+            Space::Visit,
+            Space::Property(1, 0),
+            Space::Property(1, 1),
+            Space::Rail(1),
+            Space::GoToJail,
+        ],
+        DeckDefinition::classic_chance(),
+        DeckDefinition::classic_community_chest(),
+    )
+    .unwrap();
+
+    let mut board = Board::new(boarddef, players(1, JailWait), false);
+
+    board.enable_economy(1500);
+
+    let visit = board.boarddef.find(Space::Visit);
+    let r2 = board.boarddef.find(Space::Rail(1));
+
+    // A full lap of the board back to the same space passes go
+    board.players[0].position = visit;
+
+    board.turn_with_dice(|_board, _doubles| (6, 3));
+
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].cash, 1500 + GO_SALARY);
+
+    // Two laps of the board pass go twice
+    board.players[0].position = r2;
+
+    board.turn_with_dice(|_board, _doubles| (6, 5));
+
+    assert_eq!(board.players[0].position, 0);
+    assert_eq!(board.players[0].cash, 1500 + GO_SALARY * 3);
+}
+
+#[test]
+fn test_economy_bankrupt() {
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    board.enable_economy(1500);
+
+    let b1 = board.boarddef.find(Space::Property(0, 0));
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+    let r1 = board.boarddef.find(Space::Rail(0));
+
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.owner[b2] = Some(0);
+    economy.owner[r1] = Some(1);
+    economy.mortgaged[r1] = true;
+
+    board.players[1].cash = 3;
+    board.players[1].jailcards = vec![Deck::CommunityChest];
+    board.ccdeck.retain(|card| *card != Card::GetOutOfJail);
+    board.current = 1;
+
+    // Second player lands on the first player's property and can't pay the rent. Their assets go to the first
+    // player, who pays the interest on the mortgaged station
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert!(board.players[1].is_bankrupt());
+    assert_eq!(board.players[0].cash, 1500 + 3 - 10);
+    assert_eq!(board.economy().unwrap().owner(r1), Some(0));
+    assert!(board.economy().unwrap().is_mortgaged(r1));
+    assert_eq!(board.economy().unwrap().owner(b1), None);
+    assert!(board.players[1].jail_cards().is_empty());
+    assert_eq!(board.players[0].jail_cards(), [Deck::CommunityChest]);
+    assert!(!board.ccdeck.contains(&Card::GetOutOfJail));
+    assert!(board.game_over());
+    assert_eq!(board.winner(), Some(0));
+
+    // No more turns can be taken
+    let turns = board.turns();
+
+    board.turn();
+
+    assert_eq!(board.turns(), turns);
+}
+
+#[test]
+fn test_mortgage() {
+    use event::Event;
+
+    let mut board = Board::new(BoardDefinition::default(), players(2, JailWait), false);
+
+    board.enable_economy(0);
+
+    let b1 = board.boarddef.find(Space::Property(0, 0));
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+    let r1 = board.boarddef.find(Space::Rail(0));
+
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.owner[b1] = Some(0);
+    economy.owner[b2] = Some(0);
+    economy.owner[r1] = Some(0);
+    economy.buildings[b1] = 1;
+
+    assert!(!economy.can_mortgage(&board.boarddef, 0, b2));
+    assert!(economy.can_mortgage(&board.boarddef, 0, r1));
+    assert_eq!(economy.mortgage_value(&board.boarddef, r1), 100);
+    assert_eq!(economy.unmortgage_cost(&board.boarddef, r1), 110);
+    assert_eq!(economy.unmortgage_cost(&board.boarddef, b1), 33);
+
+    // The house is sold then spaces mortgaged to pay
+    board.pay(100, Some(1));
+
+    let economy = board.economy().unwrap();
+
+    assert!(!board.players[0].bankrupt);
+    assert_eq!(board.players[0].cash, 25 + 30 + 30 + 100 - 100);
+    assert_eq!(economy.buildings(b1), 0);
+    assert!(economy.is_mortgaged(b1) && economy.is_mortgaged(b2) && economy.is_mortgaged(r1));
+
+    // No rent is paid on mortgaged spaces and sets with mortgages can't be built on
+    assert_eq!(economy.rent(&board.boarddef, r1, 7), 0);
+    assert!(!economy.can_build(&board.boarddef, 0, b1));
+
+    // Mortgages are paid off keeping cash in reserve
+    board.players[0].cash = 300;
+    board.events = Some(Vec::new());
+
+    board.unmortgage_spaces();
+
+    let economy = board.economy().unwrap();
+
+    assert_eq!(board.players[0].cash, 300 - 33 - 33);
+    assert!(!economy.is_mortgaged(b1) && !economy.is_mortgaged(b2) && economy.is_mortgaged(r1));
+    assert_eq!(economy.rent(&board.boarddef, b1, 7), 4);
+    assert!(
+        board
+            .events
+            .unwrap()
+            .contains(&Event::Unmortgaged { player: 0, space: b2 })
+    );
+}
+
+#[test]
+fn test_economy_bankrupt_to_bank() {
+    use event::Event;
+
+    let probs = vec![1.0; BoardDefinition::default().len()];
+
+    let strategies: Vec<Arc<dyn PlayerStrategy>> = vec![
+        Arc::new(JailWait),
+        Arc::new(SetTrader::new(JailWait, &BoardDefinition::default(), probs.clone()).unwrap()),
+        Arc::new(SetTrader::new(JailWait, &BoardDefinition::default(), probs).unwrap()),
+    ];
+
+    let mut board = Board::new(BoardDefinition::default(), strategies, false);
+
+    board.enable_economy(1000);
+
+    let b1 = board.boarddef.find(Space::Property(0, 0));
+    let b2 = board.boarddef.find(Space::Property(0, 1));
+
+    let economy = board.economy.as_mut().unwrap();
+
+    economy.owner[b1] = Some(0);
+    economy.owner[b2] = Some(0);
+    economy.buildings[b1] = 1;
+    economy.buildings[b2] = 1;
+
+    board.players[0].jailcards = vec![Deck::Chance];
+    board.chdeck.retain(|card| *card != Card::GetOutOfJail);
+    board.events = Some(Vec::new());
+
+    // The player goes bankrupt owing the bank
+    board.players[0].cash = 0;
+    board.bankrupt(None);
+
+    // Spaces are auctioned off and jail cards go back to the deck
+    let economy = board.economy().unwrap();
+
+    assert!(board.players[0].bankrupt);
+    assert_eq!(board.players[0].cash, 0);
+    assert_eq!(economy.buildings(b1), 0);
+    assert_eq!(economy.owner(b1), Some(2));
+    assert_eq!(economy.owner(b2), Some(2));
+    assert_eq!(board.players[2].cash, 1000 - 120);
+    assert!(!economy.is_mortgaged(b1));
+    assert!(board.chdeck.contains(&Card::GetOutOfJail));
+    assert!(!board.game_over());
+
+    let events = board.events.take().unwrap();
+
+    assert_eq!(
+        events[0],
+        Event::Bankrupt {
+            player: 0,
+            creditor: None
+        }
+    );
+    assert!(events.contains(&Event::Auctioned {
+        space: b1,
+        winner: Some(2),
+        price: 60
+    }));
+
+    // The game ends when one player is left
+    board.current = 1;
+    board.events = Some(Vec::new());
+    board.pay(5000, Some(2));
+
+    assert_eq!(board.winner(), Some(2));
+    assert_eq!(board.players[2].cash, 1000 - 120 + 1000);
+    assert!(board.events.unwrap().contains(&Event::GameOver { winner: 2, turns: 0 }));
+}
+
+#[test]
+fn test_economy_game() {
+    let mut board: Board = Board::with_seed(BoardDefinition::default(), players(2, PayJail), true, 1);
+
+    board.enable_economy(1500);
+
+    while !board.game_over() && board.turns() < 100_000 {
+        board.turn();
+
+        // Bankrupt players don't own anything
+        for (i, player) in board.players().iter().enumerate() {
+            if player.is_bankrupt() {
+                assert_eq!(player.cash(), 0);
+                assert_eq!(board.economy().unwrap().owned_by(i).count(), 0);
+            }
+        }
+    }
+
+    assert!(board.game_over());
+    assert!(board.winner().is_some());
+
+    // Playing the whole game with the same seed gives the same result
+    let mut replay: Board = Board::with_seed(BoardDefinition::default(), players(2, PayJail), true, 1);
+
+    replay.enable_economy(1500);
+
+    assert_eq!(replay.play_game(100_000), board.winner());
+    assert_eq!(replay.turns(), board.turns());
+}
+
+#[test]
+fn test_tournament() {
+    use tournament::{INITIAL_RATING, Tournament};
+
+    let strategies: Vec<Arc<dyn PlayerStrategy>> = vec![
+        Arc::new(PayJail),
+        Arc::new(JailWait),
+        Arc::new(HouseShortage::new(PayJail)),
+    ];
+
+    let tournament = Tournament::with_seed(BoardDefinition::default(), strategies.clone(), 2, 1500, 2000, 2, 1);
+
+    // Each pair of strategies plays in both seat orders
+    assert_eq!(tournament.round(), [[0, 1], [1, 0], [0, 2], [2, 0], [1, 2], [2, 1]]);
+
+    let results = tournament.run(2);
+
+    assert_eq!(results.games().len(), 12);
+
+    let finished = results.games().iter().filter(|game| game.winner.is_some()).count() as u64;
+    let standings = results.standings();
+
+    assert_eq!(standings[0].name, "Pay to leave jail");
+    assert!(standings.iter().all(|standing| standing.games == 8));
+    assert_eq!(standings.iter().map(|standing| standing.wins).sum::<u64>(), finished);
+    assert_eq!(
+        standings.iter().map(|standing| standing.unfinished).sum::<u64>(),
+        (12 - finished) * 2
+    );
+
+    // Rating changes balance out
+    let ratings = standings.iter().map(|standing| standing.rating).sum::<f64>();
+
+    assert!((ratings - INITIAL_RATING * 3.0).abs() < 1e-6);
+
+    // Results don't depend on the number of threads
+    let single = Tournament::with_seed(BoardDefinition::default(), strategies, 2, 1500, 2000, 1, 1).run(2);
+
+    assert_eq!(single.games(), results.games());
+    assert_eq!(single.standings(), results.standings());
+}
+
+#[test]
+fn test_strategy_decisions() {
+    /// Rolls on the first attempt to leave jail then pays, never buys property
+    #[derive(Debug, Clone, Copy)]
+    struct RollOnceNoBuy;
+
+    impl PlayerStrategy for RollOnceNoBuy {
+        fn name(&self) -> &str {
+            "Roll once, never buy"
+        }
+
+        fn leave_jail(&self, attempt: u8, _has_card: bool) -> JailAction {
+            if attempt == 1 {
+                JailAction::Roll
+            } else {
+                JailAction::Pay
+            }
+        }
+
+        fn buy(&self, _view: &GameView, _elem: usize) -> bool {
+            false
+        }
+    }
+
+    let mut board = Board::new(BoardDefinition::default(), players(1, RollOnceNoBuy), false);
+
+    board.enable_economy(1500);
+
+    let g2j = board.boarddef.find(Space::GoToJail);
+    let visit = board.boarddef.find(Space::Visit);
+
+    // Go to jail
+    board.players[0].position = g2j - 2;
+
+    board.turn_with_dice(|_board, _doubles| (1, 1));
+
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 1);
+
+    // First attempt - roll and fail
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 2);
+    assert_eq!(board.players[0].cash, 1500);
+
+    // Second attempt - pay and move to the property (not bought)
+    board.turn_with_dice(|_board, _doubles| (1, 3));
+
+    assert_eq!(board.players[0].position, visit + 4);
+    assert_eq!(board.players[0].jailroll, 0);
+    assert_eq!(board.players[0].cash, 1450);
+    assert_eq!(board.economy().unwrap().owner(visit + 4), None);
+}
+
+#[test]
+fn test_jail_card() {
+    let mut board = Board::new(BoardDefinition::default(), players(1, PayJail), false);
+
+    board.enable_economy(1500);
+
+    let g2j = board.boarddef.find(Space::GoToJail);
+    let visit = board.boarddef.find(Space::Visit);
+    let price = board.boarddef[visit + 3].price().unwrap();
+
+    // Land on chance 1 and keep the get out of jail free card
+    board.chdeck = VecDeque::from([Card::GetOutOfJail, Card::Inconsequential]);
+
+    board.turn_with_dice(|_board, _doubles| (3, 4));
+
+    assert_eq!(board.players[0].jail_cards(), [Deck::Chance]);
+    assert_eq!(board.chdeck, [Card::Inconsequential]);
+
+    // Go to jail
+    board.players[0].position = g2j - 2;
+
+    board.turn_with_dice(|_board, _doubles| (1, 1));
+
+    assert_eq!(board.players[0].jailroll, 1);
+
+    // Use the card instead of paying the fine. The card goes back in the deck
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.players[0].position, visit + 3);
+    assert_eq!(board.players[0].cash, 1500 - price);
+    assert!(board.players[0].jail_cards().is_empty());
+    assert_eq!(board.chdeck, [Card::Inconsequential, Card::GetOutOfJail]);
+
+    // Go to jail again and pay the fine with no card held
+    board.players[0].position = g2j - 2;
+
+    board.turn_with_dice(|_board, _doubles| (1, 1));
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+
+    assert_eq!(board.players[0].position, visit + 3);
+    assert_eq!(board.players[0].cash, 1500 - price - JAIL_FINE);
+}
+
+#[test]
+fn test_arrival_intervals() {
+    let boarddef = BoardDefinition::default();
+    let mat = crate::calc::transmatrix::TransMatrix::new(boarddef.clone(), &JailWait, false);
+
+    let mut board: Board = Board::with_seed(boarddef.clone(), players(1, JailWait), true, 1234);
+
+    // Not enough moves to estimate an interval yet
+    board.turn();
+
+    assert!(board.arrival_interval(0, 0.95).is_none());
+
+    for _ in 0..100_000 {
+        board.turn();
+    }
+
+    let batches = board.stats().batches();
+
+    assert!(batches.len() >= 32 && batches.len() < 64);
+    assert!(batches.size() * batches.len() as u64 <= board.moves());
+
+    // The calculated probability lies within nearly all of the 99% intervals
+    let (_, expected) = mat.steady_group_sum_split(|state| Some(state.position));
+
+    let mut contained = 0;
+
+    for elem in 0..boarddef.len() {
+        let interval = board.arrival_interval(elem, 0.99).unwrap();
+
+        assert_eq!(interval.estimate, board.arrivals_on(elem) as f64 / board.moves() as f64);
+        assert!(interval.std_error > 0.0);
+        assert!(interval.half_width() < 0.005);
+
+        if interval.contains(expected[elem]) {
+            contained += 1;
+        }
+    }
+
+    assert!(contained >= boarddef.len() - 3);
+
+    // Wider intervals at higher confidence
+    let jail = [boarddef.find(Space::Visit), boarddef.find(Space::GoToJail)];
+
+    let interval95 = board.stats().arrival_interval(&jail, 0.95).unwrap();
+    let interval99 = board.stats().arrival_interval(&jail, 0.99).unwrap();
+
+    assert!(interval99.half_width() > interval95.half_width());
+    assert!(interval99.contains(expected[jail[0]] + expected[jail[1]]));
+}
+
+#[test]
+fn test_parallel_runner() {
+    let boarddef = BoardDefinition::default();
+
+    let runner = runner::ParallelRunner::with_seed(boarddef.clone(), players(2, JailWait), true, 3, 1234);
+
+    assert_eq!(runner.threads(), 3);
+    assert_eq!(
+        (0..3)
+            .map(|thread| runner.thread_turns(thread, 10_000))
+            .collect::<Vec<_>>(),
+        [3334, 3333, 3333]
+    );
+
+    let stats = runner.run(10_000);
+
+    // Same as running each board separately and adding up the results
+    let mut expected = Stats::new(boarddef.len());
+
+    for thread in 0..3 {
+        let mut board: Board =
+            Board::with_seed(boarddef.clone(), players(2, JailWait), true, runner.thread_seed(thread));
+
+        for _ in 0..runner.thread_turns(thread, 10_000) {
+            board.turn();
+        }
+
+        expected.merge(board.stats());
+    }
+
+    assert_eq!(stats, expected);
+    assert_eq!(stats.turns(), 10_000);
+    assert_eq!(stats.arrivals().iter().sum::<u64>(), stats.moves());
+    assert!(stats.rollfreq().iter().sum::<u64>() >= stats.turns());
+
+    // Seeds are reproducible
+    assert_eq!(runner.run(10_000), stats);
+}
+
+#[test]
+fn test_snapshot_resume() {
+    for randomcard in [false, true] {
+        let mut board: Board = Board::with_seed(BoardDefinition::default(), players(3, JailWait), randomcard, 1234);
+
+        board.enable_economy(1500);
+
+        for _ in 0..50 {
+            board.turn();
+        }
+
+        // Save and restore through JSON
+        let json = board.snapshot().to_json();
+        let snapshot = snapshot::Snapshot::from_json(&json).unwrap();
+
+        assert_eq!(snapshot, board.snapshot());
+        assert_eq!(snapshot.players(), 3);
+
+        let mut resumed = Board::restore(snapshot, players(3, JailWait)).unwrap();
+
+        assert_eq!(resumed.snapshot(), board.snapshot());
+
+        // Both boards carry on identically
+        for _ in 0..50 {
+            board.turn();
+            resumed.turn();
+        }
+
+        assert_eq!(resumed.snapshot(), board.snapshot());
+        assert_eq!(resumed.stats(), board.stats());
+    }
+}
+
+#[test]
+fn test_snapshot_errors() {
+    use snapshot::{SNAPSHOT_VERSION, Snapshot, SnapshotError};
+
+    let board = Board::default();
+    let snapshot = board.snapshot();
+
+    // Strategies must match
+    assert!(matches!(
+        Board::restore(snapshot.clone(), players(1, JailWait)),
+        Err(SnapshotError::Invalid(_))
+    ));
+
+    assert!(matches!(
+        Board::restore(snapshot.clone(), players(2, PayJail)),
+        Err(SnapshotError::Invalid(_))
+    ));
+
+    // Unsupported version
+    let json = snapshot.to_json().replacen(
+        &format!("\"version\":{SNAPSHOT_VERSION}"),
+        &format!("\"version\":{}", SNAPSHOT_VERSION + 1),
+        1,
+    );
+
+    assert!(matches!(
+        Snapshot::from_json(&json),
+        Err(SnapshotError::Version(Some(v))) if v == SNAPSHOT_VERSION as u64 + 1
+    ));
+
+    // Older version
+    let json = snapshot.to_json().replacen(
+        &format!("\"version\":{SNAPSHOT_VERSION}"),
+        &format!("\"version\":{}", SNAPSHOT_VERSION - 1),
+        1,
+    );
+
+    assert!(matches!(
+        Snapshot::from_json(&json),
+        Err(SnapshotError::Version(Some(v))) if v == SNAPSHOT_VERSION as u64 - 1
+    ));
+
+    // Mortgages don't match the board layout
+    let mut board = Board::default();
+
+    board.enable_economy(1500);
+
+    let json = board.snapshot().to_json();
+    let mortgaged = format!("\"mortgaged\":[{}]", vec!["false"; board.boarddef.len()].join(","));
+
+    assert!(json.contains(&mortgaged));
+    assert!(matches!(
+        Snapshot::from_json(&json.replacen(&mortgaged, "\"mortgaged\":[]", 1)),
+        Err(SnapshotError::Invalid(_))
+    ));
+
+    assert!(matches!(Snapshot::from_json("{}"), Err(SnapshotError::Version(None))));
+    assert!(matches!(Snapshot::from_json("[1, 2"), Err(SnapshotError::Json(_))));
+}
+
+#[test]
+fn test_events() {
+    use event::{Event, Release};
+
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
+
+    let go = board.boarddef.find(Space::Go);
+    let ch3 = board.boarddef.find(Space::Chance(2));
+    let cc3 = board.boarddef.find(Space::CommunityChest(2));
+    let g2j = board.boarddef.find(Space::GoToJail);
+    let visit = board.boarddef.find(Space::Visit);
+
+    // Chance 3 sends us back 3 to community chest 3 which sends us to Go
+    board.players[0].position = ch3 - 5;
+
+    board.chcardchoose = |_rng, _deck| Card::Back(3);
+    board.cccardchoose = |_rng, _deck| Card::GoTo(Space::Go);
+
+    board.events = Some(Vec::new());
+    board.turn_with_dice(|_board, _doubles| (2, 3));
+
+    assert_eq!(
+        board.events.take().unwrap(),
+        [
+            Event::TurnStarted { player: 0, turn: 1 },
+            Event::DiceRolled {
+                player: 0,
+                roll: Roll::new(&[2, 3], None)
+            },
+            Event::Moved {
+                player: 0,
+                from: ch3 - 5,
+                to: ch3,
+                reason: MoveReason::Roll
+            },
+            Event::CardDrawn {
+                player: 0,
+                deck: Deck::Chance,
+                card: Card::Back(3)
+            },
+            Event::Moved {
+                player: 0,
+                from: ch3,
+                to: cc3,
+                reason: MoveReason::CHCard
+            },
+            Event::CardDrawn {
+                player: 0,
+                deck: Deck::CommunityChest,
+                card: Card::GoTo(Space::Go)
+            },
+            Event::PassedGo { player: 0 },
+            Event::Moved {
+                player: 0,
+                from: cc3,
+                to: go,
+                reason: MoveReason::CHCardCCCard
+            },
+            Event::TurnEnded { player: 0, doubles: 0 },
+        ]
+    );
+
+    // Go to jail, fail to roll a double then roll one
+    board.players[0].position = g2j - 2;
+
+    board.events = Some(Vec::new());
+    board.turn_with_dice(|_board, _doubles| (1, 1));
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+    board.turn_with_dice(|_board, _doubles| (3, 3));
+
+    let events = board.events.take().unwrap();
+
+    assert!(events.contains(&Event::Moved {
+        player: 0,
+        from: g2j,
+        to: visit,
+        reason: MoveReason::GoToJail
+    }));
+    assert!(events.contains(&Event::SentToJail {
+        player: 0,
+        reason: MoveReason::GoToJail
+    }));
+    assert!(events.contains(&Event::StayedInJail { player: 0, attempt: 1 }));
+    assert!(events.contains(&Event::ReleasedFromJail {
+        player: 0,
+        release: Release::Double
+    }));
+    assert_eq!(board.players[0].position, visit + 6);
+}
+
+#[test]
+fn test_json_lines() {
+    use std::io::Write;
+    use std::sync::Mutex;
+
+    /// Buffer shared with the observer
+    #[derive(Debug, Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buffer = Shared::default();
+
+    let mut board1: Board = Board::with_seed(BoardDefinition::default(), players(2, JailWait), true, 1234);
+    let mut board2: Board = Board::with_seed(BoardDefinition::default(), players(2, JailWait), true, 1234);
+
+    board1
+        .set_observer(Box::new(event::JsonLinesWriter::new(buffer.clone())))
+        .unwrap();
+
+    let mut events = Vec::new();
+
+    for _ in 0..100 {
+        board1.turn();
+        events.extend(board2.turn_events());
+    }
+
+    board1.remove_observer().unwrap();
+
+    // Events are the same whether observed or collected, one per line
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), events.len());
+    assert_eq!(
+        events
+            .iter()
+            .filter(|e| matches!(e, event::Event::TurnStarted { .. }))
+            .count(),
+        100
+    );
+
+    for (line, event) in lines.iter().zip(&events) {
+        assert_eq!(*line, serde_json::to_string(event).unwrap());
+    }
+
+    assert!(lines[0].starts_with(r#"{"event":"TurnStarted","player":0,"turn":1}"#));
+}
+
+#[test]
+fn test_rules_movement() {
+    let rules = Rules {
+        triple_double_jail: false,
+        jail_attempts: 2,
+        ..Rules::classic()
+    };
+
+    let mut board = Board::new(BoardDefinition::default(), players(1, JailWait), false);
+
+    board.set_rules(rules);
+
+    let g2j = board.boarddef.find(Space::GoToJail);
+    let visit = board.boarddef.find(Space::Visit);
+
+    // Third double ends the turn without going to jail (income tax, Vermont Avenue, electric company)
+    board.turn_with_dice(|_board, _doubles| (2, 2));
+
+    assert_eq!(board.players[0].position, 12);
+    assert_eq!(board.players[0].jailroll, 0);
+    assert_eq!(board.stats.doubles, [0, 0, 1]);
+    assert_eq!(board.stats.moves, 3);
+
+    // Go to jail then leave after the second failed roll
+    board.players[0].position = g2j - 3;
+
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+    assert_eq!(board.players[0].jailroll, 1);
+
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+    assert_eq!(board.players[0].jailroll, 2);
+
+    board.turn_with_dice(|_board, _doubles| (1, 2));
+    assert_eq!(board.players[0].position, visit);
+    assert_eq!(board.players[0].jailroll, 0);
+    assert_eq!(board.stats.arrival_reason[g2j][MoveReason::NoDouble as usize], 1);
+    assert_eq!(board.stats.arrival_reason[visit][MoveReason::ExitJail as usize], 1);
+}
+
+#[test]
+fn test_rules_money() {
+    let rules = Rules {
+        free_parking_pot: true,
+        double_go_salary: true,
+        ..Rules::classic()
+    };
+
+    let mut board = Board::new(BoardDefinition::default(), players(1, PayJail), false);
+
+    board.set_rules(rules);
+    board.enable_economy(1500);
+
+    let tax = board.boarddef[4].tax().unwrap();
+
+    // Tax is paid in to the pot
+    board.turn_with_dice(|_board, _doubles| (1, 3));
+
+    assert_eq!(board.players[0].cash, 1500 - tax);
+    assert_eq!(board.economy().unwrap().pot(), tax);
+
+    // Landing on free parking collects the pot
+    board.players[0].position = 16;
+    board.turn_with_dice(|_board, _doubles| (1, 3));
+
+    assert_eq!(board.players[0].cash, 1500);
+    assert_eq!(board.economy().unwrap().pot(), 0);
+
+    // Landing on go doubles the salary
+    board.players[0].position = 36;
+    board.turn_with_dice(|_board, _doubles| (1, 3));
+
+    assert_eq!(board.players[0].position, 0);
+    assert_eq!(board.players[0].cash, 1500 + 2 * GO_SALARY);
+
+    // Passing go doesn't (then buying Baltic Avenue)
+    let price = board.boarddef[3].price().unwrap();
+
+    board.players[0].position = 37;
+    board.turn_with_dice(|_board, _doubles| (1, 5));
+
+    assert_eq!(board.players[0].position, 3);
+    assert_eq!(board.players[0].cash, 1500 + 3 * GO_SALARY - price);
+}
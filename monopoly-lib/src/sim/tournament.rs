@@ -0,0 +1,321 @@
+use std::{sync::Arc, thread};
+
+use itertools::Itertools;
+use rand::random;
+
+use crate::boarddef::BoardDefinition;
+use crate::dice::DiceModel;
+use crate::rules::Rules;
+use crate::strategy::PlayerStrategy;
+
+use super::{Board, SimRng};
+
+/// Rating given to every strategy at the start of a tournament
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// Maximum rating change for a game between two players
+const RATING_K: f64 = 32.0;
+
+/// Round robin tournament of whole games between player strategies
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    boarddef: BoardDefinition,
+    strategies: Vec<Arc<dyn PlayerStrategy>>,
+    seats: usize,
+    randomcard: bool,
+    dice: DiceModel,
+    rules: Rules,
+    cash: u32,
+    max_turns: u64,
+    threads: usize,
+    seed: u64,
+}
+
+impl Tournament {
+    /// Create a new tournament between strategies with a number of players (seats) in each game, starting cash,
+    /// turn limit per game and number of threads (0 to use all available) using a random seed
+    pub fn new(
+        boarddef: BoardDefinition,
+        strategies: Vec<Arc<dyn PlayerStrategy>>,
+        seats: usize,
+        cash: u32,
+        max_turns: u64,
+        threads: usize,
+    ) -> Self {
+        Self::with_seed(boarddef, strategies, seats, cash, max_turns, threads, random())
+    }
+
+    /// Create a new tournament between strategies with a number of players (seats) in each game, starting cash,
+    /// turn limit per game and number of threads (0 to use all available). Each game is seeded from the seed given
+    /// so tournaments with the same seed produce identical results whatever the number of threads
+    pub fn with_seed(
+        boarddef: BoardDefinition,
+        strategies: Vec<Arc<dyn PlayerStrategy>>,
+        seats: usize,
+        cash: u32,
+        max_turns: u64,
+        threads: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(seats >= 2, "At least two players are required in each game");
+        assert!(strategies.len() >= seats, "Not enough strategies to fill the seats");
+
+        let threads = if threads == 0 {
+            thread::available_parallelism().map_or(1, |n| n.get())
+        } else {
+            threads
+        };
+
+        Self {
+            boarddef,
+            strategies,
+            seats,
+            randomcard: false,
+            dice: DiceModel::classic(),
+            rules: Rules::classic(),
+            cash,
+            max_turns,
+            threads,
+            seed,
+        }
+    }
+
+    /// Choose cards randomly instead of cycling the decks
+    pub fn set_randomcard(&mut self, randomcard: bool) {
+        self.randomcard = randomcard;
+    }
+
+    /// Use a different dice model in every game
+    pub fn set_dice(&mut self, dice: DiceModel) {
+        self.dice = dice;
+    }
+
+    /// Use different rule variants in every game
+    pub fn set_rules(&mut self, rules: Rules) {
+        rules.validate().expect("Invalid rules");
+
+        self.rules = rules;
+    }
+
+    /// Returns the number of threads used
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Returns the random number generator seed in use
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the seating for each game in a round. Every combination of strategies plays once with each
+    /// rotation of the seats
+    pub fn round(&self) -> Vec<Vec<usize>> {
+        (0..self.strategies.len())
+            .combinations(self.seats)
+            .flat_map(|strategies| {
+                (0..self.seats).map(move |rotation| {
+                    let mut seating = strategies.clone();
+                    seating.rotate_left(rotation);
+                    seating
+                })
+            })
+            .collect()
+    }
+
+    /// Plays a number of rounds, returning the results of every game and the standings of the strategies
+    pub fn run(&self, rounds: u64) -> TournamentResults {
+        let round = self.round();
+        let games = round.len() * rounds as usize;
+
+        let mut results = thread::scope(|scope| {
+            let handles = (0..self.threads)
+                .map(|thread| {
+                    let round = &round;
+
+                    scope.spawn(move || {
+                        (thread..games)
+                            .step_by(self.threads)
+                            .map(|game| (game, self.play(game, &round[game % round.len()])))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Tournament thread panicked"))
+                .collect::<Vec<_>>()
+        });
+
+        // Put the games back in order so the ratings don't depend on the number of threads
+        results.sort_by_key(|(game, _)| *game);
+
+        TournamentResults::new(
+            &self.strategies,
+            results.into_iter().map(|(_, result)| result).collect(),
+        )
+    }
+
+    /// Plays one game with a seating of strategies
+    fn play(&self, game: usize, seating: &[usize]) -> GameResult {
+        let strategies = seating
+            .iter()
+            .map(|&strategy| self.strategies[strategy].clone())
+            .collect();
+
+        let mut board: Board<SimRng> = Board::with_seed(
+            self.boarddef.clone(),
+            strategies,
+            self.randomcard,
+            self.seed.wrapping_add(game as u64),
+        );
+
+        board.set_dice(self.dice.clone());
+        board.set_rules(self.rules);
+        board.enable_economy(self.cash);
+
+        let winner = board.play_game(self.max_turns);
+
+        GameResult {
+            seating: seating.to_vec(),
+            winner,
+            cash: board.players().iter().map(|player| player.cash()).collect(),
+            turns: board.turns(),
+        }
+    }
+}
+
+/// Result of a tournament game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameResult {
+    /// Strategy in each seat
+    pub seating: Vec<usize>,
+    /// Winning seat (None if the turn limit was reached)
+    pub winner: Option<usize>,
+    /// Cash held by the player in each seat at the end of the game
+    pub cash: Vec<u32>,
+    /// Number of turns taken by all players
+    pub turns: u64,
+}
+
+/// Totals for a strategy over a tournament
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    /// Strategy name
+    pub name: String,
+    /// Number of games played
+    pub games: u64,
+    /// Number of games won
+    pub wins: u64,
+    /// Number of games which reached the turn limit
+    pub unfinished: u64,
+    /// Total cash held at the end of the games
+    pub cash: u64,
+    /// Total turns taken by all players in the games
+    pub turns: u64,
+    /// Elo style rating
+    pub rating: f64,
+}
+
+impl Standing {
+    /// Returns the fraction of games won
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games as f64
+    }
+
+    /// Returns the average cash held at the end of a game
+    pub fn average_cash(&self) -> f64 {
+        self.cash as f64 / self.games as f64
+    }
+
+    /// Returns the average number of turns in a game
+    pub fn average_turns(&self) -> f64 {
+        self.turns as f64 / self.games as f64
+    }
+}
+
+/// Results of a tournament
+#[derive(Debug, Clone)]
+pub struct TournamentResults {
+    games: Vec<GameResult>,
+    standings: Vec<Standing>,
+}
+
+impl TournamentResults {
+    /// Total up the game results for each strategy
+    fn new(strategies: &[Arc<dyn PlayerStrategy>], games: Vec<GameResult>) -> Self {
+        let mut standings = strategies
+            .iter()
+            .map(|strategy| Standing {
+                name: strategy.name().to_string(),
+                games: 0,
+                wins: 0,
+                unfinished: 0,
+                cash: 0,
+                turns: 0,
+                rating: INITIAL_RATING,
+            })
+            .collect::<Vec<_>>();
+
+        for game in &games {
+            for (seat, &strategy) in game.seating.iter().enumerate() {
+                let standing = &mut standings[strategy];
+
+                standing.games += 1;
+                standing.wins += (game.winner == Some(seat)) as u64;
+                standing.unfinished += game.winner.is_none() as u64;
+                standing.cash += game.cash[seat] as u64;
+                standing.turns += game.turns;
+            }
+
+            Self::update_ratings(&mut standings, game);
+        }
+
+        Self { games, standings }
+    }
+
+    /// Update the ratings after a game. Each pair of players is scored as a game between two players, the winner
+    /// beating everyone else and the other pairs drawing, with the rating change shared between the pairs
+    fn update_ratings(standings: &mut [Standing], game: &GameResult) {
+        let k = RATING_K / (game.seating.len() - 1) as f64;
+
+        let changes = game
+            .seating
+            .iter()
+            .enumerate()
+            .map(|(seat, &strategy)| {
+                game.seating
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != seat)
+                    .map(|(other, &opponent)| {
+                        let expected =
+                            1.0 / (1.0 + 10f64.powf((standings[opponent].rating - standings[strategy].rating) / 400.0));
+
+                        let score = match game.winner {
+                            Some(winner) if winner == seat => 1.0,
+                            Some(winner) if winner == other => 0.0,
+                            _ => 0.5,
+                        };
+
+                        k * (score - expected)
+                    })
+                    .sum::<f64>()
+            })
+            .collect::<Vec<_>>();
+
+        for (&strategy, change) in game.seating.iter().zip(changes) {
+            standings[strategy].rating += change;
+        }
+    }
+
+    /// Returns the results of every game
+    pub fn games(&self) -> &[GameResult] {
+        &self.games
+    }
+
+    /// Returns the standings for each strategy in the order the strategies were given
+    pub fn standings(&self) -> &[Standing] {
+        &self.standings
+    }
+}
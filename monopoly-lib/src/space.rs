@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use strum::Display;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum Space {
     Go,
     Visit,
@@ -37,37 +38,100 @@ impl Space {
         }
     }
 
-    pub fn find(space: Space) -> usize {
-        SPACES
-            .iter()
-            .position(|s| *s == space)
-            .unwrap_or_else(|| panic!("Space {space:?} not found"))
+    /// Returns the purchase price of the space if it can be bought
+    pub fn price(&self) -> Option<u32> {
+        match self {
+            Property(set, i) => STREETS.get(*set as usize)?.get(*i as usize).map(|(price, _)| *price),
+            Rail(_) => Some(200),
+            Utility(_) => Some(150),
+            _ => None,
+        }
     }
 
-    pub fn next_rail(position: usize) -> usize {
-        Self::find_next(position, |s| matches!(s, Space::Rail(_)))
+    /// Returns the rent table for the space if it can be bought
+    pub fn rent(&self) -> Option<Rent> {
+        match self {
+            Property(set, i) => STREETS
+                .get(*set as usize)?
+                .get(*i as usize)
+                .map(|(_, rent)| Rent::Property(*rent)),
+            Rail(_) => Some(Rent::Station([25, 50, 100, 200])),
+            Utility(_) => Some(Rent::Utility([4, 10])),
+            _ => None,
+        }
     }
 
-    pub fn next_util(position: usize) -> usize {
-        Self::find_next(position, |s| matches!(s, Space::Utility(_)))
+    /// Returns the cost of building a house (or hotel) on the space if it can be built on
+    pub fn house_cost(&self) -> Option<u32> {
+        match self {
+            Property(set, _) => HOUSE_COST.get(*set as usize).copied(),
+            _ => None,
+        }
     }
 
-    fn find_next<F>(position: usize, check: F) -> usize
-    where
-        F: Fn(&Space) -> bool,
-    {
-        for i in (position + 1)..(position + SPACES.len()) {
-            let elem = i % SPACES.len();
-
-            if check(&SPACES[elem]) {
-                return elem;
-            }
+    /// Returns the amount of tax to pay when landing on the space
+    pub fn tax(&self) -> Option<u32> {
+        match self {
+            Tax(0) => Some(200),
+            Tax(_) => Some(100),
+            _ => None,
         }
-
-        panic!("Next space not found")
     }
 }
 
+/// Rent table for a space which can be bought
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Rent {
+    /// Rent with no houses, 1 to 4 houses and a hotel. Rent with no houses is doubled when the whole set is owned
+    Property([u32; 6]),
+    /// Rent when 1 to 4 stations are owned
+    Station([u32; 4]),
+    /// Dice roll multiplier when 1 or 2 utilities are owned
+    Utility([u32; 2]),
+}
+
+/// Price and rent table for each street by set
+const STREETS: [&[(u32, [u32; 6])]; 8] = [
+    &[(60, [2, 10, 30, 90, 160, 250]), (60, [4, 20, 60, 180, 320, 450])],
+    &[
+        (100, [6, 30, 90, 270, 400, 550]),
+        (100, [6, 30, 90, 270, 400, 550]),
+        (120, [8, 40, 100, 300, 450, 600]),
+    ],
+    &[
+        (140, [10, 50, 150, 450, 625, 750]),
+        (140, [10, 50, 150, 450, 625, 750]),
+        (160, [12, 60, 180, 500, 700, 900]),
+    ],
+    &[
+        (180, [14, 70, 200, 550, 750, 950]),
+        (180, [14, 70, 200, 550, 750, 950]),
+        (200, [16, 80, 220, 600, 800, 1000]),
+    ],
+    &[
+        (220, [18, 90, 250, 700, 875, 1050]),
+        (220, [18, 90, 250, 700, 875, 1050]),
+        (240, [20, 100, 300, 750, 925, 1100]),
+    ],
+    &[
+        (260, [22, 110, 330, 800, 975, 1150]),
+        (260, [22, 110, 330, 800, 975, 1150]),
+        (280, [24, 120, 360, 850, 1025, 1200]),
+    ],
+    &[
+        (300, [26, 130, 390, 900, 1100, 1275]),
+        (300, [26, 130, 390, 900, 1100, 1275]),
+        (320, [28, 150, 450, 1000, 1200, 1400]),
+    ],
+    &[
+        (350, [35, 175, 500, 1100, 1300, 1500]),
+        (400, [50, 200, 600, 1400, 1700, 2000]),
+    ],
+];
+
+/// Cost of a house or hotel by set
+const HOUSE_COST: [u32; 8] = [50, 50, 100, 100, 150, 150, 200, 200];
+
 impl std::fmt::Display for Space {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let desc = match self {
@@ -135,7 +199,7 @@ pub const SPACES: [Space; SPACECOUNT] = [
 ];
 
 #[repr(u8)]
-#[derive(Debug, Display, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[derive(Debug, Display, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum PropertySet {
     Brown,
     LightBlue,
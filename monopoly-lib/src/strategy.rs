@@ -1,5 +1,419 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+use serde::Serialize;
+
+use crate::boarddef::BoardDefinition;
+use crate::calc::probability::Probability;
+use crate::sim::economy::{Economy, HOTEL};
+use crate::sim::player::Player;
+use crate::space::PropertySet;
+
+/// Number of attempts a player has at rolling a double to leave jail
+pub const JAIL_ATTEMPTS: u8 = 3;
+
+/// Cash kept in hand by the default building and mortgage repayment decisions
+const BUILD_RESERVE: u32 = 200;
+
+/// Method of leaving jail
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub enum Strategy {
-    PayJail,
-    JailWait,
+pub enum JailAction {
+    /// Pay the fine and roll as normal
+    Pay,
+    /// Try to roll a double
+    Roll,
+    /// Use a get out of jail free card and roll as normal (the fine is paid if no card is held)
+    UseCard,
+    /// Try to roll a double with a given probability, otherwise use a get out of jail free card if held or pay
+    Mixed(Probability),
 }
+
+impl JailAction {
+    /// Returns the probability of trying to roll a double
+    pub fn roll_probability(&self) -> Probability {
+        match self {
+            JailAction::Roll => Probability::ALWAYS,
+            JailAction::Pay | JailAction::UseCard => Probability::NEVER,
+            JailAction::Mixed(prob) => *prob,
+        }
+    }
+}
+
+/// Information available to a strategy when making a decision
+pub struct GameView<'a> {
+    /// Board layout
+    pub boarddef: &'a BoardDefinition,
+    /// Property ownership and buildings
+    pub economy: &'a Economy,
+    /// Players in the game
+    pub players: &'a [Player],
+    /// Index of the player making the decision
+    pub player: usize,
+}
+
+impl GameView<'_> {
+    /// Returns the cash held by the player making the decision
+    pub fn cash(&self) -> u32 {
+        self.players[self.player].cash()
+    }
+}
+
+/// Exchange of spaces and cash proposed by one player to another
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct Trade {
+    /// Player the trade is proposed to
+    pub partner: usize,
+    /// Spaces given to the partner
+    pub give: Vec<usize>,
+    /// Spaces taken from the partner
+    pub take: Vec<usize>,
+    /// Cash given to the partner
+    pub give_cash: u32,
+    /// Cash taken from the partner
+    pub take_cash: u32,
+}
+
+/// Player strategy consulted by the simulator and calculator at each decision point
+pub trait PlayerStrategy: Debug + Send + Sync {
+    /// Returns the name of the strategy
+    fn name(&self) -> &str;
+
+    /// Chooses how to leave jail on a given attempt (1 to 3). This must only depend on the attempt and card so
+    /// the Markov chain calculation can use it
+    fn leave_jail(&self, attempt: u8, has_card: bool) -> JailAction;
+
+    /// Decides whether to buy an unowned space. Only asked when the player can afford the price
+    fn buy(&self, _view: &GameView, _elem: usize) -> bool {
+        true
+    }
+
+    /// Chooses a space to build a house or hotel on from a list of affordable candidates (None to stop building)
+    fn build(&self, view: &GameView, candidates: &[usize]) -> Option<usize> {
+        candidates.iter().copied().find(|&elem| {
+            view.boarddef[elem]
+                .house_cost()
+                .is_some_and(|cost| view.cash() >= cost + BUILD_RESERVE)
+        })
+    }
+
+    /// Chooses a space to sell a building on from a list of candidates to raise an amount of cash (None to not sell)
+    fn sell(&self, _view: &GameView, candidates: &[usize], _needed: u32) -> Option<usize> {
+        candidates.first().copied()
+    }
+
+    /// Chooses a space to mortgage from a list of candidates to raise an amount of cash (None to not mortgage)
+    fn mortgage(&self, _view: &GameView, candidates: &[usize], _needed: u32) -> Option<usize> {
+        candidates.first().copied()
+    }
+
+    /// Chooses a mortgaged space to pay off from a list of affordable candidates (None to stop paying off)
+    fn unmortgage(&self, view: &GameView, candidates: &[usize]) -> Option<usize> {
+        candidates
+            .iter()
+            .copied()
+            .find(|&elem| view.cash() >= view.economy.unmortgage_cost(view.boarddef, elem) + BUILD_RESERVE)
+    }
+
+    /// Returns a bid for a space at auction given the current highest bid (None to drop out)
+    fn bid(&self, _view: &GameView, _elem: usize, _highest: u32) -> Option<u32> {
+        None
+    }
+
+    /// Proposes a trade to another player at the end of a turn (None to not trade)
+    fn propose_trade(&self, _view: &GameView) -> Option<Trade> {
+        None
+    }
+
+    /// Decides whether to accept a trade proposed by another player. The trade gives and takes are from the
+    /// proposing player's side
+    fn accept_trade(&self, _view: &GameView, _proposer: usize, _trade: &Trade) -> bool {
+        false
+    }
+}
+
+/// Always leave jail straight away, using a get out of jail free card if held otherwise paying
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct PayJail;
+
+impl PlayerStrategy for PayJail {
+    fn name(&self) -> &str {
+        "Pay to leave jail"
+    }
+
+    fn leave_jail(&self, _attempt: u8, has_card: bool) -> JailAction {
+        if has_card { JailAction::UseCard } else { JailAction::Pay }
+    }
+}
+
+/// Always try to roll a double to leave jail
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct JailWait;
+
+impl PlayerStrategy for JailWait {
+    fn name(&self) -> &str {
+        "Roll to leave jail"
+    }
+
+    fn leave_jail(&self, _attempt: u8, _has_card: bool) -> JailAction {
+        JailAction::Roll
+    }
+}
+
+/// Roll to leave jail for a number of attempts then use a get out of jail free card or pay
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct JailRolls(pub u8);
+
+impl PlayerStrategy for JailRolls {
+    fn name(&self) -> &str {
+        "Roll a number of times to leave jail then pay"
+    }
+
+    fn leave_jail(&self, attempt: u8, has_card: bool) -> JailAction {
+        if attempt <= self.0 {
+            JailAction::Roll
+        } else if has_card {
+            JailAction::UseCard
+        } else {
+            JailAction::Pay
+        }
+    }
+}
+
+/// Roll to leave jail with a given probability for each attempt, otherwise use a card or pay
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MixedJail(pub [Probability; JAIL_ATTEMPTS as usize]);
+
+impl PlayerStrategy for MixedJail {
+    fn name(&self) -> &str {
+        "Randomly roll or pay to leave jail"
+    }
+
+    fn leave_jail(&self, attempt: u8, _has_card: bool) -> JailAction {
+        JailAction::Mixed(self.0[attempt as usize - 1])
+    }
+}
+
+/// Builds up to four houses on each space but never upgrades to a hotel, keeping houses out of the bank to cause
+/// a house shortage for the other players. Otherwise plays like another strategy
+#[derive(Debug)]
+pub struct HouseShortage<S> {
+    strategy: S,
+    name: String,
+}
+
+impl<S: PlayerStrategy> HouseShortage<S> {
+    /// Create a new house shortage strategy based on another strategy
+    pub fn new(strategy: S) -> Self {
+        let name = format!("{} (house shortage)", strategy.name());
+
+        Self { strategy, name }
+    }
+}
+
+impl<S: PlayerStrategy> PlayerStrategy for HouseShortage<S> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn leave_jail(&self, attempt: u8, has_card: bool) -> JailAction {
+        self.strategy.leave_jail(attempt, has_card)
+    }
+
+    fn buy(&self, view: &GameView, elem: usize) -> bool {
+        self.strategy.buy(view, elem)
+    }
+
+    fn build(&self, view: &GameView, candidates: &[usize]) -> Option<usize> {
+        let houses = candidates
+            .iter()
+            .copied()
+            .filter(|&elem| view.economy.buildings(elem) < HOTEL - 1)
+            .collect::<Vec<_>>();
+
+        self.strategy.build(view, &houses)
+    }
+
+    fn sell(&self, view: &GameView, candidates: &[usize], needed: u32) -> Option<usize> {
+        self.strategy.sell(view, candidates, needed)
+    }
+
+    fn mortgage(&self, view: &GameView, candidates: &[usize], needed: u32) -> Option<usize> {
+        self.strategy.mortgage(view, candidates, needed)
+    }
+
+    fn unmortgage(&self, view: &GameView, candidates: &[usize]) -> Option<usize> {
+        self.strategy.unmortgage(view, candidates)
+    }
+
+    fn bid(&self, view: &GameView, elem: usize, highest: u32) -> Option<u32> {
+        self.strategy.bid(view, elem, highest)
+    }
+
+    fn propose_trade(&self, view: &GameView) -> Option<Trade> {
+        self.strategy.propose_trade(view)
+    }
+
+    fn accept_trade(&self, view: &GameView, proposer: usize, trade: &Trade) -> bool {
+        self.strategy.accept_trade(view, proposer, trade)
+    }
+}
+
+/// Values spaces by their price weighted by how often they are landed on (for example using the steady state
+/// from `TransMatrix`). Bids up to the value of a space at auction, offers twice the value of the last space needed
+/// to complete a set and accepts trades giving at least the value given up, counted twice for a space which
+/// completes a set for the other player. Otherwise plays like another strategy
+#[derive(Debug)]
+pub struct SetTrader<S> {
+    strategy: S,
+    probs: Vec<f64>,
+    name: String,
+}
+
+impl<S: PlayerStrategy> SetTrader<S> {
+    /// Create a new set trading strategy based on another strategy with the probability of landing on each space of
+    /// a board layout. Fails if there isn't a probability for every space
+    pub fn new(strategy: S, boarddef: &BoardDefinition, probs: Vec<f64>) -> Result<Self, SetTraderError> {
+        if probs.len() != boarddef.len() {
+            return Err(SetTraderError::Length {
+                probs: probs.len(),
+                spaces: boarddef.len(),
+            });
+        }
+
+        let name = format!("{} (set trader)", strategy.name());
+
+        Ok(Self { strategy, probs, name })
+    }
+
+    /// Returns the value of a space. A space landed on as often as average is valued at its price
+    pub fn value(&self, boarddef: &BoardDefinition, elem: usize) -> u32 {
+        let price = boarddef[elem].price().unwrap_or(0) as f64;
+        let total = self.probs.iter().sum::<f64>();
+
+        if total > 0.0 {
+            (price * self.probs[elem] * self.probs.len() as f64 / total).round() as u32
+        } else {
+            price as u32
+        }
+    }
+
+    /// Returns the positions of the buildable spaces in a set
+    fn set_spaces(boarddef: &BoardDefinition, set: PropertySet) -> impl Iterator<Item = usize> {
+        (0..boarddef.len()).filter(move |&elem| boarddef[elem].set() == set && boarddef[elem].house_cost().is_some())
+    }
+}
+
+impl<S: PlayerStrategy> PlayerStrategy for SetTrader<S> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn leave_jail(&self, attempt: u8, has_card: bool) -> JailAction {
+        self.strategy.leave_jail(attempt, has_card)
+    }
+
+    fn buy(&self, view: &GameView, elem: usize) -> bool {
+        self.strategy.buy(view, elem)
+    }
+
+    fn build(&self, view: &GameView, candidates: &[usize]) -> Option<usize> {
+        self.strategy.build(view, candidates)
+    }
+
+    fn sell(&self, view: &GameView, candidates: &[usize], needed: u32) -> Option<usize> {
+        self.strategy.sell(view, candidates, needed)
+    }
+
+    fn mortgage(&self, view: &GameView, candidates: &[usize], needed: u32) -> Option<usize> {
+        self.strategy.mortgage(view, candidates, needed)
+    }
+
+    fn unmortgage(&self, view: &GameView, candidates: &[usize]) -> Option<usize> {
+        self.strategy.unmortgage(view, candidates)
+    }
+
+    fn bid(&self, view: &GameView, elem: usize, highest: u32) -> Option<u32> {
+        // Raise the bid by 10 up to the value of the space
+        let bid = (highest + 10).min(self.value(view.boarddef, elem)).min(view.cash());
+
+        (bid > highest).then_some(bid)
+    }
+
+    fn propose_trade(&self, view: &GameView) -> Option<Trade> {
+        let economy = view.economy;
+
+        // Look for a set with one space missing which is owned by another player
+        (0..view.boarddef.len())
+            .filter(|&elem| view.boarddef[elem].house_cost().is_some())
+            .find_map(|elem| {
+                let owner = economy.owner(elem)?;
+
+                if owner == view.player
+                    || view.players[owner].is_bankrupt()
+                    || !economy.can_trade(view.boarddef, owner, elem)
+                    || !Self::set_spaces(view.boarddef, view.boarddef[elem].set())
+                        .all(|other| other == elem || economy.owner(other) == Some(view.player))
+                {
+                    return None;
+                }
+
+                let offer = self.value(view.boarddef, elem) * 2;
+
+                (offer <= view.cash()).then(|| Trade {
+                    partner: owner,
+                    take: vec![elem],
+                    give_cash: offer,
+                    ..Default::default()
+                })
+            })
+    }
+
+    fn accept_trade(&self, view: &GameView, proposer: usize, trade: &Trade) -> bool {
+        let economy = view.economy;
+
+        // Spaces the proposer gives are received, spaces the proposer takes are given up
+        let received = trade.give_cash
+            + trade
+                .give
+                .iter()
+                .map(|&elem| self.value(view.boarddef, elem))
+                .sum::<u32>();
+
+        let given = trade.take_cash
+            + trade
+                .take
+                .iter()
+                .map(|&elem| {
+                    let completes = Self::set_spaces(view.boarddef, view.boarddef[elem].set())
+                        .all(|other| economy.owner(other) == Some(proposer) || trade.take.contains(&other));
+
+                    let value = self.value(view.boarddef, elem);
+
+                    if completes { value * 2 } else { value }
+                })
+                .sum::<u32>();
+
+        received >= given
+    }
+}
+
+/// Set trading strategy creation error
+#[derive(Debug, PartialEq, Eq)]
+pub enum SetTraderError {
+    Length { probs: usize, spaces: usize },
+}
+
+impl Display for SetTraderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SetTraderError::Length { probs, spaces } => {
+                write!(
+                    f,
+                    "{probs} landing probabilities given for a board with {spaces} spaces"
+                )
+            }
+        }
+    }
+}
+
+impl Error for SetTraderError {}
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+pub struct Cli {
+    /// Strategies to play against each other (all if not given)
+    #[arg(short, long, value_enum)]
+    pub strategy: Vec<StrategyArg>,
+
+    /// Number of players in each game
+    #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u16).range(2..))]
+    pub players: u16,
+
+    /// Number of rounds to play. Each round plays every combination of strategies once in each seat order
+    #[arg(short, long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub rounds: u64,
+
+    /// Starting cash for each player
+    #[arg(short, long, default_value_t = 1500)]
+    pub cash: u32,
+
+    /// Maximum number of turns (all players) before a game is abandoned
+    #[arg(short, long, default_value_t = 5000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_turns: u64,
+
+    /// Number of threads to use (all available if not given)
+    #[arg(short, long, default_value_t = 0)]
+    pub threads: usize,
+
+    /// Random number generator seed (random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Board definition file (TOML or JSON) to use instead of the classic board
+    #[arg(short, long)]
+    pub board: Option<PathBuf>,
+
+    /// Dice model file (TOML or JSON) to use instead of two six sided dice
+    #[arg(long)]
+    pub dice: Option<PathBuf>,
+
+    /// Rules file (TOML or JSON) with house rules to use instead of the classic rules
+    #[arg(long)]
+    pub rules: Option<PathBuf>,
+}
+
+/// Strategies which can take part in the tournament
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StrategyArg {
+    /// Pay to leave jail straight away
+    Pay,
+    /// Roll to leave jail
+    Wait,
+    /// Roll once to leave jail then pay
+    RollOnce,
+    /// Pay to leave jail and never build hotels to cause a house shortage
+    Shortage,
+    /// Pay to leave jail, bid at auctions and trade to complete sets
+    Trader,
+}
@@ -0,0 +1,154 @@
+use std::{error::Error, sync::Arc};
+
+use clap::{Parser, ValueEnum};
+use cli::{Cli, StrategyArg};
+use monopoly_lib::boarddef::BoardDefinition;
+use monopoly_lib::calc::transmatrix::TransMatrix;
+use monopoly_lib::dice::DiceModel;
+use monopoly_lib::rules::Rules;
+use monopoly_lib::sim::tournament::Tournament;
+use monopoly_lib::strategy::{HouseShortage, JailRolls, JailWait, PayJail, PlayerStrategy, SetTrader, SetTraderError};
+
+mod cli;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    // Load the board layout
+    let boarddef = match &cli.board {
+        Some(file) => BoardDefinition::load(file)?,
+        None => BoardDefinition::default(),
+    };
+
+    // Load the dice model
+    let dice = match &cli.dice {
+        Some(file) => DiceModel::load(file)?,
+        None => DiceModel::classic(),
+    };
+
+    // Load the rule variants
+    let rules = match &cli.rules {
+        Some(file) => Rules::load(file)?,
+        None => Rules::classic(),
+    };
+
+    // Default to every strategy
+    let strategy_args = if cli.strategy.is_empty() {
+        StrategyArg::value_variants().to_vec()
+    } else {
+        cli.strategy.clone()
+    };
+
+    if strategy_args.len() < cli.players as usize {
+        return Err(format!(
+            "At least {} strategies are needed for {} players",
+            cli.players, cli.players
+        )
+        .into());
+    }
+
+    let strategies = strategy_args
+        .iter()
+        .map(|arg| create_strategy(*arg, &boarddef, &dice, rules))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Set up the tournament
+    let mut tournament = match cli.seed {
+        Some(seed) => Tournament::with_seed(
+            boarddef,
+            strategies,
+            cli.players as usize,
+            cli.cash,
+            cli.max_turns,
+            cli.threads,
+            seed,
+        ),
+        None => Tournament::new(
+            boarddef,
+            strategies,
+            cli.players as usize,
+            cli.cash,
+            cli.max_turns,
+            cli.threads,
+        ),
+    };
+
+    tournament.set_dice(dice);
+    tournament.set_rules(rules);
+
+    let games = tournament.round().len() as u64 * cli.rounds;
+
+    println!(
+        "Playing {games} games on {} threads (seed {})",
+        tournament.threads(),
+        tournament.seed()
+    );
+
+    if rules != Rules::classic() {
+        println!("House rules: {rules}");
+    }
+
+    println!();
+
+    let results = tournament.run(cli.rounds);
+
+    // Print the standings, highest rating first
+    let mut standings = results.standings().iter().collect::<Vec<_>>();
+
+    standings.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+
+    let width = standings
+        .iter()
+        .map(|standing| standing.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Strategy".len());
+
+    println!(
+        "{:<width$}  {:>8}  {:>7}  {:>10}  {:>9}  {:>10}  {:>7}",
+        "Strategy", "Games", "Win %", "Unfinished", "Avg cash", "Avg turns", "Elo"
+    );
+
+    for standing in standings {
+        println!(
+            "{:<width$}  {:>8}  {:>7.2}  {:>10}  {:>9.0}  {:>10.1}  {:>7.0}",
+            standing.name,
+            standing.games,
+            standing.win_rate() * 100.0,
+            standing.unfinished,
+            standing.average_cash(),
+            standing.average_turns(),
+            standing.rating
+        );
+    }
+
+    Ok(())
+}
+
+/// Creates a player strategy from a command line argument
+fn create_strategy(
+    arg: StrategyArg,
+    boarddef: &BoardDefinition,
+    dice: &DiceModel,
+    rules: Rules,
+) -> Result<Arc<dyn PlayerStrategy>, SetTraderError> {
+    let strategy: Arc<dyn PlayerStrategy> = match arg {
+        StrategyArg::Pay => Arc::new(PayJail),
+        StrategyArg::Wait => Arc::new(JailWait),
+        StrategyArg::RollOnce => Arc::new(JailRolls(1)),
+        StrategyArg::Shortage => Arc::new(HouseShortage::new(PayJail)),
+        StrategyArg::Trader => {
+            // Value spaces by the steady state probability of being on them
+            let transmatrix = TransMatrix::with_rules(boarddef.clone(), &PayJail, dice.clone(), rules, false);
+            let steady = transmatrix.steady_group_sum(|state| Some(state.position));
+
+            let probs = (0..boarddef.len())
+                .map(|elem| steady.get(&elem).copied().unwrap_or(0.0))
+                .collect();
+
+            Arc::new(SetTrader::new(PayJail, boarddef, probs)?)
+        }
+    };
+
+    Ok(strategy)
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::Parser;
 
 #[derive(Parser)]
@@ -6,4 +8,33 @@ pub struct Cli {
     /// Roll to get out of jail
     #[arg(short, long)]
     pub wait: bool,
+
+    /// Number of players
+    #[arg(short = 'n', long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    pub players: u16,
+
+    /// Random number generator seed (random if not given)
+    #[arg(short, long)]
+    pub seed: Option<u64>,
+
+    /// Session file saved to by pressing 's'
+    #[arg(short = 'f', long, default_value = "monopoly-session.json")]
+    pub session: PathBuf,
+
+    /// Resume the session saved in the session file
+    #[arg(short, long)]
+    pub resume: bool,
+
+    /// Write every game event to a JSON Lines file
+    #[arg(short, long)]
+    pub log: Option<PathBuf>,
+
+    /// Dice model file (TOML or JSON) to use instead of two six sided dice. Resumed sessions keep their dice
+    #[arg(short, long)]
+    pub dice: Option<PathBuf>,
+
+    /// Rules file (TOML or JSON) with house rules to use instead of the classic rules. Resumed sessions keep their
+    /// rules
+    #[arg(long)]
+    pub rules: Option<PathBuf>,
 }
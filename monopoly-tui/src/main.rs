@@ -1,6 +1,9 @@
 use std::{
     error::Error,
-    io::{self, stdout},
+    fs::File,
+    io::{self, BufWriter, stdout},
+    path::PathBuf,
+    sync::Arc,
     time::Duration,
 };
 
@@ -15,8 +18,13 @@ use crossterm::{
     tty::IsTty,
 };
 
-use monopoly_lib::{movereason::MoveReason, sim::Board, space::SPACES};
-use monopoly_lib::{space::Space, strategy::Strategy};
+use monopoly_lib::dice::DiceModel;
+use monopoly_lib::rules::Rules;
+use monopoly_lib::sim::event::JsonLinesWriter;
+use monopoly_lib::sim::snapshot::Snapshot;
+use monopoly_lib::space::Space;
+use monopoly_lib::strategy::{JailWait, PayJail, PlayerStrategy};
+use monopoly_lib::{boarddef::BoardDefinition, movereason::MoveReason, sim::Board};
 use num_traits::{FromPrimitive, Num, NumCast};
 use numformat::NumFormat;
 
@@ -31,6 +39,46 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Parse command line arguments
     let cli = Cli::parse();
 
+    // All players use the same strategy
+    let strategy: Arc<dyn PlayerStrategy> = if cli.wait {
+        Arc::new(JailWait)
+    } else {
+        Arc::new(PayJail)
+    };
+
+    let mut board = if cli.resume {
+        // Resume the saved session
+        let snapshot = Snapshot::load(&cli.session)?;
+        let strategies = vec![strategy; snapshot.players()];
+
+        Board::restore(snapshot, strategies)?
+    } else {
+        // Create the classic board, cards pulled in order
+        let strategies = vec![strategy; cli.players as usize];
+
+        let mut board = match cli.seed {
+            Some(seed) => Board::with_seed(BoardDefinition::default(), strategies, false, seed),
+            None => Board::new(BoardDefinition::default(), strategies, false),
+        };
+
+        // Load the dice model
+        if let Some(dice) = &cli.dice {
+            board.set_dice(DiceModel::load(dice)?);
+        }
+
+        // Load the rule variants
+        if let Some(rules) = &cli.rules {
+            board.set_rules(Rules::load(rules)?);
+        }
+
+        board
+    };
+
+    // Log events if requested
+    if let Some(log) = &cli.log {
+        board.set_observer(Box::new(JsonLinesWriter::new(BufWriter::new(File::create(log)?))))?;
+    }
+
     // Get stdout
     let mut stdout = stdout();
 
@@ -41,14 +89,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     terminal::enable_raw_mode()?;
 
     // Play the game
-    game_loop(
-        &mut stdout,
-        if cli.wait {
-            Strategy::JailWait
-        } else {
-            Strategy::PayJail
-        },
-    )?;
+    game_loop(&mut stdout, &mut board, cli.session)?;
 
     // Reset the terminal
     execute!(stdout, style::ResetColor, cursor::Show, terminal::LeaveAlternateScreen)?;
@@ -56,6 +97,9 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Disable raw mode
     terminal::disable_raw_mode()?;
 
+    // Finish writing the event log
+    board.remove_observer()?;
+
     Ok(())
 }
 
@@ -65,23 +109,33 @@ struct State {
     paused: bool,
     dirty_draw: bool,
     split_jail: bool,
+    save: bool,
+    message: String,
 }
 
-fn game_loop<W>(w: &mut W, strategy: Strategy) -> io::Result<()>
+fn game_loop<W>(w: &mut W, board: &mut Board, session: PathBuf) -> io::Result<()>
 where
     W: io::Write,
 {
     // Create state
     let mut state = State::default();
 
-    // Create the board, cards pulled in order
-    let mut board = Board::new(strategy, false);
-
     // Play the game
     while !state.terminate {
+        if state.save {
+            // Save the session
+            state.message = match board.snapshot().save(&session) {
+                Ok(()) => format!("Saved to {}", session.display()),
+                Err(e) => e.to_string(),
+            };
+
+            state.save = false;
+            state.dirty_draw = false;
+        }
+
         if !state.dirty_draw {
             // Clear and redraw the screen
-            initialise_screen(w, &board, &state)?;
+            initialise_screen(w, board, &state)?;
             state.dirty_draw = true;
         }
 
@@ -97,13 +151,13 @@ where
             }
 
             // Draw the board
-            draw(w, &board, state.split_jail)?;
+            draw(w, board, state.split_jail)?;
 
             // Flush output
             w.flush()?;
 
             // Auto pause?
-            if board.turns() % 100_000_000 == 0 {
+            if board.turns().is_multiple_of(100_000_000) {
                 state.paused = true;
             }
 
@@ -137,6 +191,7 @@ fn process_event(event: Event, state: &mut State) -> io::Result<()> {
             match c {
                 'q' => state.terminate = true,
                 'p' => state.paused = !state.paused,
+                's' => state.save = true,
                 'j' => {
                     state.split_jail = !state.split_jail;
                     state.dirty_draw = false;
@@ -162,7 +217,7 @@ where
     execute!(w, terminal::Clear(ClearType::All))?;
 
     // Draw instructions
-    draw_instructions(w)?;
+    draw_instructions(w, board, &state.message)?;
 
     // Draw the board
     draw(w, board, state.split_jail)?;
@@ -173,12 +228,12 @@ where
     Ok(())
 }
 
-fn draw_instructions<W>(w: &mut W) -> io::Result<()>
+fn draw_instructions<W>(w: &mut W, board: &Board, message: &str) -> io::Result<()>
 where
     W: io::Write,
 {
-    let mut draw_instruction_line = |y: &mut u16, line| -> io::Result<()> {
-        queue!(w, cursor::MoveTo(6, *y + 13), style::Print(format!("{:^55}", line)),)?;
+    let mut draw_instruction_line = |y: &mut u16, line: &str| -> io::Result<()> {
+        queue!(w, cursor::MoveTo(6, *y + 13), style::Print(format!("{:^55.55}", line)),)?;
 
         *y += 1;
 
@@ -192,9 +247,17 @@ where
     draw_instruction_line(&mut y, "Calculates the probability of landing")?;
     draw_instruction_line(&mut y, "on each space by simulating moves")?;
     y += 1;
+    draw_instruction_line(
+        &mut y,
+        &format!("Players {}  Seed {}", board.players().len(), board.seed()),
+    )?;
+    y += 1;
     draw_instruction_line(&mut y, "Press 'q' to exit")?;
     draw_instruction_line(&mut y, "Press 'p' to toggle pause")?;
     draw_instruction_line(&mut y, "Press 'j' to toggle Just Visiting")?;
+    draw_instruction_line(&mut y, "Press 's' to save the session")?;
+    y += 1;
+    draw_instruction_line(&mut y, message)?;
 
     Ok(())
 }
@@ -262,9 +325,9 @@ where
     };
 
     let mut draw_space = |x, y, elem| -> io::Result<()> {
-        let desc = space_desc(elem);
+        let space = &board.boarddef()[elem];
 
-        draw_space_int(x, y, desc, board.arrivals_on(elem), &SPACES[elem])
+        draw_space_int(x, y, space_desc(space), board.arrivals_on(elem), space)
     };
 
     // Top row
@@ -291,8 +354,8 @@ where
         draw_space(XPAD, YPAD + ((i + 1) * YSPACE), elem)?;
     }
 
-    let visit_elem = Space::find(Space::Visit);
-    let g2j_elem = Space::find(Space::GoToJail);
+    let visit_elem = board.boarddef().find(Space::Visit);
+    let g2j_elem = board.boarddef().find(Space::GoToJail);
 
     let jail = board.arrivals_on(g2j_elem);
     let visit = board.arrivals_on(visit_elem);
@@ -304,14 +367,14 @@ where
             YPAD,
             "VISIT".to_string(),
             visit,
-            &SPACES[visit_elem],
+            &board.boarddef()[visit_elem],
         )?;
         draw_space_int(
             XPAD + (9 * XSPACE),
             YPAD + YSPACE,
             "JAIL".to_string(),
             jail,
-            &SPACES[visit_elem],
+            &board.boarddef()[visit_elem],
         )?;
     } else {
         // Combined jail
@@ -320,12 +383,18 @@ where
             YPAD,
             "JAIL".to_string(),
             visit + jail,
-            &SPACES[visit_elem],
+            &board.boarddef()[visit_elem],
         )?;
     }
 
     // Draw go to jail
-    draw_space_int(XPAD, YPAD + (10 * YSPACE), "G2J".to_string(), 0, &SPACES[30])?;
+    draw_space_int(
+        XPAD,
+        YPAD + (10 * YSPACE),
+        "G2J".to_string(),
+        0,
+        &board.boarddef()[g2j_elem],
+    )?;
 
     Ok(())
 }
@@ -351,7 +420,11 @@ where
         let pct = percent(value, total);
 
         draw_stat(w, y, desc, value)?;
-        queue!(w, style::Print(format!("  ({:.dp$}%)  ", pct,)),)?;
+        queue!(
+            w,
+            style::Print(format!("  ({:.dp$}%)  ", pct,)),
+            terminal::Clear(ClearType::UntilNewLine)
+        )?;
 
         Ok(())
     };
@@ -389,17 +462,17 @@ where
 
     blank_line(w, &mut y)?;
 
-    let g2j = Space::find(Space::GoToJail);
-    let visit = Space::find(Space::Visit);
+    let g2j = board.boarddef().find(Space::GoToJail);
+    let visit = board.boarddef().find(Space::Visit);
 
     let mut sorted = if split_jail {
         board
             .arrivals()
             .iter()
             .enumerate()
-            .map(|(i, a)| match SPACES[i] {
-                Space::Visit => (*a, i, 2),                            // Just visiting
-                Space::GoToJail => (*a, Space::find(Space::Visit), 1), // Jail
+            .map(|(i, a)| match board.boarddef()[i] {
+                Space::Visit => (*a, i, 2),        // Just visiting
+                Space::GoToJail => (*a, visit, 1), // Jail
                 _ => (*a, i, 0),
             })
             .collect::<Vec<_>>()
@@ -418,12 +491,23 @@ where
     sorted.sort();
 
     for (a, elem, sub) in sorted.into_iter().rev().take(10) {
-        let desc = space_desc(elem);
+        let desc = space_desc(&board.boarddef()[elem]);
         draw_stat_pct(w, &mut y, desc.as_str(), a, board.moves(), 2)?;
 
+        // Show the 95% confidence interval once enough moves have been made
+        let elems = match (&board.boarddef()[elem], sub) {
+            (Space::Visit, 0) => vec![visit, g2j], // Combined jail
+            (Space::Visit, 1) => vec![g2j],        // Jail (split)
+            _ => vec![elem],
+        };
+
+        if let Some(interval) = board.stats().arrival_interval(&elems, 0.95) {
+            queue!(w, style::Print(format!("±{:.2}%", interval.half_width() * 100.0)))?;
+        }
+
         queue!(w, style::SetAttribute(style::Attribute::Dim),)?;
 
-        let arrivals_elem = if SPACES[elem] == Space::Visit {
+        let arrivals_elem = if board.boarddef()[elem] == Space::Visit {
             match sub {
                 0 => {
                     // Combined jail
@@ -469,8 +553,8 @@ fn percent<I: Num + NumCast>(value: I, total: I) -> f64 {
     }
 }
 
-fn space_desc(elem: usize) -> String {
-    match SPACES[elem] {
+fn space_desc(space: &Space) -> String {
+    match *space {
         Space::Go => "GO".to_string(),
         Space::Visit => "JAIL".to_string(),
         Space::FreeParking => "FREE".to_string(),
@@ -1,16 +1,19 @@
 use monopoly_lib::{
+    boarddef::BoardDefinition,
     calc::transmatrix::TransMatrix,
     movereason::MoveReason,
     sim::Board,
-    space::{SPACECOUNT, SPACES, Space},
-    strategy::Strategy,
+    space::Space,
+    strategy::{JailWait, PayJail, PlayerStrategy},
 };
+use std::sync::Arc;
 use strum::IntoEnumIterator;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub struct WasmBoard {
     board: Board,
+    jailwait: bool,
 }
 
 #[wasm_bindgen]
@@ -26,6 +29,9 @@ pub struct WasmStats {
     pub reasons_stride: usize,
     #[wasm_bindgen(getter_with_clone)]
     pub reasons: Vec<u64>,
+    #[wasm_bindgen(getter_with_clone)]
+    pub arrival_ci: Vec<f64>,
+    pub jail_ci: Option<f64>,
     pub jailwait: bool,
 }
 
@@ -38,6 +44,26 @@ impl WasmBoard {
             self.board.turn();
         }
 
+        // 95% confidence interval half widths for each space and for jail and just visiting combined
+        let half_width = |elems: &[usize]| {
+            self.board
+                .stats()
+                .arrival_interval(elems, 0.95)
+                .map(|interval| interval.half_width())
+        };
+
+        let spaces = self.board.boarddef().len();
+
+        let arrival_ci = (0..spaces)
+            .map(|elem| half_width(&[elem]))
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+
+        let jail_ci = half_width(&[
+            self.board.boarddef().find(Space::Visit),
+            self.board.boarddef().find(Space::GoToJail),
+        ]);
+
         // Return stats
         WasmStats {
             turns: self.board.turns(),
@@ -52,13 +78,17 @@ impl WasmBoard {
                 .iter()
                 .flat_map(|arr| arr.iter().copied())
                 .collect(),
-            jailwait: self.board.strategy() == Strategy::JailWait,
+            arrival_ci,
+            jail_ci,
+            jailwait: self.jailwait,
         }
     }
 
     /// Get spaces
     pub fn get_spaces(&self) -> Vec<String> {
-        SPACES
+        self.board
+            .boarddef()
+            .spaces()
             .iter()
             .map(|s| match s {
                 Space::Go => "G".to_string(),
@@ -75,6 +105,11 @@ impl WasmBoard {
             .collect()
     }
 
+    /// Get random number generator seed
+    pub fn get_seed(&self) -> u64 {
+        self.board.seed()
+    }
+
     /// Get number of turns performed
     pub fn get_turns(&self) -> u64 {
         self.board.turns()
@@ -89,18 +124,20 @@ impl WasmBoard {
 }
 
 #[wasm_bindgen]
-pub fn create_board(jailwait: bool) -> WasmBoard {
-    // Create board with requested strategy, cards pulled at random
-    WasmBoard {
-        board: Board::new(
-            if jailwait {
-                Strategy::JailWait
-            } else {
-                Strategy::PayJail
-            },
-            true,
-        ),
-    }
+pub fn create_board(jailwait: bool, seed: Option<u64>) -> WasmBoard {
+    let strategy: Arc<dyn PlayerStrategy> = if jailwait {
+        Arc::new(JailWait)
+    } else {
+        Arc::new(PayJail)
+    };
+
+    // Create classic board with one player and requested strategy, cards pulled at random
+    let board = match seed {
+        Some(seed) => Board::with_seed(BoardDefinition::default(), vec![strategy], true, seed),
+        None => Board::new(BoardDefinition::default(), vec![strategy], true),
+    };
+
+    WasmBoard { board, jailwait }
 }
 
 #[wasm_bindgen]
@@ -115,15 +152,9 @@ pub struct WasmFreq {
 #[wasm_bindgen]
 pub fn get_expected_frequencies(jailwait: bool) -> WasmFreq {
     // Build probability matrices
-    let transmatrix = TransMatrix::new(
-        if jailwait {
-            Strategy::JailWait
-        } else {
-            Strategy::PayJail
-        },
-        6,
-        false,
-    );
+    let strategy: &dyn PlayerStrategy = if jailwait { &JailWait } else { &PayJail };
+
+    let transmatrix = TransMatrix::new(BoardDefinition::default(), strategy, false);
 
     // Sum the steady state matrix by position
     let (_, mat) = transmatrix.steady_group_sum_split(|state| Some(state.position));
@@ -142,6 +173,6 @@ pub fn get_expected_frequencies(jailwait: bool) -> WasmFreq {
     WasmFreq {
         space_prob,
         movereason_prob,
-        movereason_prob_stride: SPACECOUNT,
+        movereason_prob_stride: transmatrix.boarddef().len(),
     }
 }
@@ -18,6 +18,9 @@ let split_just_visiting = true;
 let full_leaderboard = false;
 let showreasons = true;
 
+// Random number generator seed (from the 'seed' URL parameter if given)
+let seed;
+
 // Worker thread object
 let worker;
 
@@ -91,6 +94,9 @@ function process_worker_message(msg) {
                 console.debug(`Got ${msg.data.msgtype} from worker:`, msg.data);
             }
 
+            // Log the seed so runs can be reproduced
+            console.info(`Random number generator seed: ${msg.data.seed}`);
+
             if (msg.data.msgtype == "initfin") {
                 // Set up the board after first initialise
                 setup_page(msg.data);
@@ -178,8 +184,17 @@ function worker_init(first) {
     // Clear any stats
     last_stats = undefined;
 
+    // Get seed from the URL on first initialise
+    if (first) {
+        const seedparam = new URLSearchParams(window.location.search).get("seed");
+
+        if (seedparam) {
+            seed = BigInt(seedparam);
+        }
+    }
+
     // Tell worker to (re)initialise
-    worker.postMessage({ msgtype: (first ? "init" : "reinit"), jailwait: jailwait, debug: workerdebug })
+    worker.postMessage({ msgtype: (first ? "init" : "reinit"), jailwait: jailwait, seed: seed, debug: workerdebug })
 }
 
 // Get the worker to execute a chunk
@@ -713,7 +728,7 @@ function update_percentages_and_leaderboard(stats) {
         switch (space_codes[index][0]) {
             case 'J': // Just visiting
                 if (split_just_visiting) {
-                    leaderboard.push([index, arrivals, 2]);
+                    leaderboard.push([index, arrivals, 2, stats.arrival_ci[index]]);
                 }
 
                 break;
@@ -722,25 +737,25 @@ function update_percentages_and_leaderboard(stats) {
 
                 if (split_just_visiting) {
                     // Jail (visit sub 2)
-                    leaderboard.push([space_visit, arrivals, 1]);
+                    leaderboard.push([space_visit, arrivals, 1, stats.arrival_ci[index]]);
                 } else {
                     // Combined jail + just visiting
-                    leaderboard.push([space_visit, arrivals + stats.arrivals[space_visit], 0]);
+                    leaderboard.push([space_visit, arrivals + stats.arrivals[space_visit], 0, stats.jail_ci]);
                 }
 
                 break;
             default:
-                leaderboard.push([index, arrivals, 0]);
+                leaderboard.push([index, arrivals, 0, stats.arrival_ci[index]]);
         }
     };
 
     // Sort by arrivals
-    leaderboard.sort(([_ia, aa, _sa], [_ib, ab, _sb]) => Number(ab - aa));
+    leaderboard.sort(([_ia, aa, _sa, _ca], [_ib, ab, _sb, _cb]) => Number(ab - aa));
 
     // Draw colour ranked percentages on board spaces
     const hue_split = 180 / leaderboard.length;
 
-    for (const [rank, [index, arrivals, sub]] of leaderboard.entries()) {
+    for (const [rank, [index, arrivals, sub, ci]] of leaderboard.entries()) {
         let id;
 
         if (sub == 0) {
@@ -754,6 +769,9 @@ function update_percentages_and_leaderboard(stats) {
 
         elem.style.backgroundColor = colour;
         elem.innerText = percent(arrivals, stats.moves);
+
+        // Show the 95% confidence interval when hovering once enough moves have been made
+        elem.title = ci === undefined ? "" : `±${percent_fmt(ci)} (95% confidence)`;
     };
 
     // Clear the leaderboard table
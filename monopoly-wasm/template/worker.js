@@ -101,11 +101,12 @@ function initialise(msg) {
     exec_target = 0;
 
     // Create the board
-    board = create_board(msg.jailwait);
+    board = create_board(msg.jailwait, msg.seed);
 
     // Send result to main thread
     let ret = {
-        msgtype: `${msg.msgtype}fin`
+        msgtype: `${msg.msgtype}fin`,
+        seed: board.get_seed()
     };
 
     if (msg.msgtype == "init") {
@@ -201,6 +202,8 @@ function build_jstats(rstats) {
         rollfreq: rstats.rollfreq,
         arrivals: rstats.arrivals,
         reasons: reasons,
+        arrival_ci: rstats.arrival_ci,
+        jail_ci: rstats.jail_ci,
     }
 }
 